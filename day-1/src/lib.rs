@@ -0,0 +1,292 @@
+use std::{cmp::Reverse, io::BufRead};
+
+use aho_corasick::AhoCorasick;
+use anyhow::{bail, Context, Result};
+
+/// The words that spell out a digit in a calibration document
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    /// Every word with the digit it spells out, longest words first
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// The names of the built-in vocabularies, see [`Vocabulary::builtin`]
+    pub const BUILTIN: [&'static str; 3] = ["english", "dutch", "german"];
+
+    /// A vocabulary from words and the digits they spell out
+    pub fn new(words: impl IntoIterator<Item = (String, u32)>) -> Result<Self> {
+        let mut words = words.into_iter().collect::<Vec<_>>();
+        for (word, digit) in words.iter() {
+            if word.is_empty() || word.chars().any(|c| c.is_ascii_digit()) {
+                bail!("Expected a word without digits, got: {:?}", word);
+            }
+            if *digit > 9 {
+                bail!(
+                    "Expected {} to spell out a single digit, got: {}",
+                    word,
+                    digit
+                );
+            }
+        }
+
+        // when one word starts or ends with another, like "eins" and "ein",
+        // matching the longest word first finds the word that was meant
+        words.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        words.dedup();
+        if let Some(pair) = words.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            bail!("Expected {} to spell out a single digit", pair[0].0);
+        }
+        Ok(Self { words })
+    }
+
+    /// One, two, ..., nine
+    pub fn english() -> Self {
+        Self::from_words([
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ])
+    }
+
+    /// Een, twee, ..., negen
+    pub fn dutch() -> Self {
+        Self::from_words([
+            "een", "twee", "drie", "vier", "vijf", "zes", "zeven", "acht", "negen",
+        ])
+    }
+
+    /// Eins, zwei, ..., neun
+    pub fn german() -> Self {
+        Self::from_words([
+            "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+        ])
+    }
+
+    /// The built-in vocabulary of a language: english, dutch or german
+    pub fn builtin(language: &str) -> Result<Self> {
+        match language.to_lowercase().as_str() {
+            "english" => Ok(Self::english()),
+            "dutch" => Ok(Self::dutch()),
+            "german" => Ok(Self::german()),
+            _ => bail!(
+                "Expected one of {}, got: {}",
+                Self::BUILTIN.join(", "),
+                language
+            ),
+        }
+    }
+
+    /// Parse a vocabulary file, with a `word=digit` pair on every line.
+    /// Empty lines and lines starting with `#` are skipped.
+    pub fn parse(input: &str) -> Result<Self> {
+        let words = input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let (word, digit) = line
+                    .split_once('=')
+                    .with_context(|| format!("Expected word=digit, got: {}", line))?;
+                let digit = digit
+                    .trim()
+                    .parse()
+                    .with_context(|| format!("Expected a digit, got: {}", digit))?;
+                Ok((word.trim().to_string(), digit))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(words)
+    }
+
+    /// The words of one to nine, in order
+    fn from_words(words: [&str; 9]) -> Self {
+        Self::new(words.into_iter().map(str::to_string).zip(1..)).unwrap()
+    }
+}
+
+/// A digit or a word that spells out a digit, found in a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    /// Byte offset of the first byte of the token
+    pub start: usize,
+    /// Byte offset just past the last byte of the token
+    pub end: usize,
+    pub digit: u32,
+}
+
+/// The first and last tokens of a line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Calibration {
+    /// The first and last digit, for part one
+    pub digits: Option<(Token, Token)>,
+    /// The first and last digit or word, for part two
+    pub words: Option<(Token, Token)>,
+}
+
+impl Calibration {
+    pub fn part_one(&self) -> Option<u32> {
+        self.digits
+            .map(|(first, last)| first.digit * 10 + last.digit)
+    }
+
+    pub fn part_two(&self) -> Option<u32> {
+        self.words
+            .map(|(first, last)| first.digit * 10 + last.digit)
+    }
+
+    /// Both calibration values, or an error when the line has no digit
+    pub fn values(&self) -> Result<(u32, u32)> {
+        let part_one = self.part_one().context("Expected atleast 1 digit input")?;
+        let part_two = self
+            .part_two()
+            .context("Expected line to have atleast 1 match")?;
+        Ok((part_one, part_two))
+    }
+}
+
+/// Finds the first and last digits and words of a line in a single pass over its bytes.
+/// Every occurrence of every word is found, also where words overlap like in "twone".
+pub struct Scanner {
+    automaton: AhoCorasick,
+    /// The digit of every pattern of the automaton
+    digits: Vec<u32>,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Result<Self> {
+        // the digits themselves are the first ten patterns
+        let (patterns, digits): (Vec<String>, Vec<u32>) = (0..10)
+            .map(|digit| (digit.to_string(), digit))
+            .chain(vocabulary.words.iter().cloned())
+            .unzip();
+        let automaton =
+            AhoCorasick::new(patterns).context("Could not build a scanner for the vocabulary")?;
+        Ok(Self { automaton, digits })
+    }
+
+    pub fn scan(&self, line: &[u8]) -> Calibration {
+        let mut calibration = Calibration::default();
+        for m in self.automaton.find_overlapping_iter(line) {
+            let token = Token {
+                start: m.start(),
+                end: m.end(),
+                digit: self.digits[m.pattern()],
+            };
+            if m.pattern().as_usize() < 10 {
+                extend(&mut calibration.digits, token);
+            }
+            extend(&mut calibration.words, token);
+        }
+        calibration
+    }
+}
+
+/// Keep the first and the last token, where one word starts with another the longest word wins
+fn extend(first_last: &mut Option<(Token, Token)>, token: Token) {
+    let Some((first, last)) = first_last else {
+        *first_last = Some((token, token));
+        return;
+    };
+    if (token.start, Reverse(token.end)) < (first.start, Reverse(first.end)) {
+        *first = token;
+    }
+    if (token.start, token.end) > (last.start, last.end) {
+        *last = token;
+    }
+}
+
+/// Parse the calibration document into the calibration values of each line,
+/// as a pair of (part one, part two) values.
+pub fn parse_input(input: &str) -> Result<Vec<(u32, u32)>> {
+    parse_input_with(input, &Vocabulary::english())
+}
+
+/// Like [`parse_input`], with the digits of part two spelled out in the words of `vocabulary`
+pub fn parse_input_with(input: &str, vocabulary: &Vocabulary) -> Result<Vec<(u32, u32)>> {
+    let scanner = Scanner::new(vocabulary)?;
+    input
+        .lines()
+        .map(|line| scanner.scan(line.as_bytes()).values())
+        .collect()
+}
+
+/// Scan a document one line at a time, calling `on_line` with the number, the bytes
+/// and the tokens of every line. Line numbers start at 1, line endings are left out.
+pub fn scan_lines(
+    mut reader: impl BufRead,
+    vocabulary: &Vocabulary,
+    mut on_line: impl FnMut(usize, &[u8], Calibration) -> Result<()>,
+) -> Result<()> {
+    let scanner = Scanner::new(vocabulary)?;
+    let mut line = vec![];
+    let mut number = 0;
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        number += 1;
+        let trimmed = line.strip_suffix(b"\n").unwrap_or(&line);
+        let trimmed = trimmed.strip_suffix(b"\r").unwrap_or(trimmed);
+        on_line(number, trimmed, scanner.scan(trimmed))?;
+        line.clear();
+    }
+    Ok(())
+}
+
+/// Sum the calibration values of both parts of a document, reading it one line at a time
+pub fn sum_calibration_values(reader: impl BufRead, vocabulary: &Vocabulary) -> Result<(u64, u64)> {
+    let (mut sum_part_one, mut sum_part_two) = (0, 0);
+    scan_lines(reader, vocabulary, |number, _line, calibration| {
+        let (part_one, part_two) = calibration
+            .values()
+            .with_context(|| format!("Line {}", number))?;
+        sum_part_one += part_one as u64;
+        sum_part_two += part_two as u64;
+        Ok(())
+    })?;
+    Ok((sum_part_one, sum_part_two))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part_two(line: &str, vocabulary: &Vocabulary) -> u32 {
+        parse_input_with(line, vocabulary).unwrap()[0].1
+    }
+
+    #[test]
+    fn overlapping_words() {
+        assert_eq!(part_two("7twone", &Vocabulary::english()), 71);
+        assert_eq!(part_two("eightwo3", &Vocabulary::english()), 83);
+        assert_eq!(part_two("2zevenegen", &Vocabulary::dutch()), 29);
+        assert_eq!(part_two("1achtwee", &Vocabulary::dutch()), 12);
+        assert_eq!(part_two("3sechsiebenull", &Vocabulary::german()), 37);
+        assert_eq!(part_two("fünf4", &Vocabulary::german()), 54);
+    }
+
+    #[test]
+    fn vocabulary_files() {
+        let vocabulary = Vocabulary::parse("# spelled out\nein = 1\neins=1\n\nelf=9\n").unwrap();
+        // the longest word wins where one word starts with another
+        assert_eq!(part_two("7eins", &vocabulary), 71);
+        assert_eq!(part_two("7einself", &vocabulary), 79);
+        assert_eq!(part_two("7one", &vocabulary), 77);
+
+        assert!(Vocabulary::parse("one").is_err());
+        assert!(Vocabulary::parse("one=10").is_err());
+        assert!(Vocabulary::parse("on3=3").is_err());
+        assert!(Vocabulary::parse("one=1\none=2").is_err());
+    }
+
+    /// Reading line by line gives the same sums as parsing the whole document
+    #[test]
+    fn streaming_sums() {
+        let input = "two1nine\r\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
+        let values = parse_input(input).unwrap();
+        let sums = values
+            .iter()
+            .fold((0, 0), |(a, b), (c, d)| (a + *c as u64, b + *d as u64));
+        let streamed = sum_calibration_values(input.as_bytes(), &Vocabulary::english()).unwrap();
+        assert_eq!(streamed, sums);
+        assert_eq!(streamed.1, 198);
+
+        let error = sum_calibration_values("1\nnine\n".as_bytes(), &Vocabulary::english());
+        assert_eq!(error.unwrap_err().to_string(), "Line 2");
+    }
+}
@@ -1,79 +1,135 @@
-use std::env;
+use std::{
+    env, fs,
+    io::{self, BufRead, BufWriter, Write},
+    path::PathBuf,
+};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
-use regex::Regex;
+use clap::Parser;
+use day_1::{scan_lines, sum_calibration_values, Token, Vocabulary};
 
-fn main() -> Result<()> {
-    let mut args: Vec<String> = env::args().collect();
+/// Trebuchet?!
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The puzzle input, or - to read it from stdin
+    #[arg()]
+    input: Option<String>,
 
-    let input = if args.len() > 1 {
-        args.pop().unwrap()
-    } else {
-        get_puzzle_input(2023, 1, None).context("Could not retrieve puzzle input")?
-    };
+    /// Advent of code session token
+    #[arg(short, long)]
+    session: Option<String>,
 
-    let re = Regex::new(r"\d|one|two|three|four|five|six|seven|eight|nine").unwrap();
+    /// The language digits are spelled out in for part two: english, dutch or german
+    #[arg(short, long, default_value = "english")]
+    language: String,
 
-    // greedy match any character that comes before it when searching for the right part of the number
-    // otherwise, twone will match "two" instead of "one"
-    let re_from_right = Regex::new(r".*(\d|one|two|three|four|five|six|seven|eight|nine)").unwrap();
+    /// A file with a word=digit pair on every line, to spell out digits in instead of --language
+    #[arg(short, long)]
+    words: Option<PathBuf>,
 
-    let mut sum_part_one = 0;
-    let mut sum_part_two = 0;
+    /// List the first and last token of every line with their byte spans and values,
+    /// and flag lines without a digit instead of stopping at them
+    #[arg(short, long)]
+    report: bool,
+}
 
-    for line in input.lines() {
-        // part one
-        let first_digit_from_left = line
-            .chars()
-            .find(|c| c.is_digit(10))
-            .context("Expected atleast 1 digit input")?
-            .to_digit(10)
-            .unwrap();
-        let first_digit_from_right = line
-            .chars()
-            .rev()
-            .find(|c| c.is_digit(10))
-            .context("Expected atleast 1 digit input")?
-            .to_digit(10)
-            .unwrap();
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let session = args.session.or_else(|| env::var("SESSION").ok());
 
-        sum_part_one += first_digit_from_left * 10 + first_digit_from_right;
+    let vocabulary = match args.words {
+        Some(path) => {
+            let words = fs::read_to_string(&path)
+                .with_context(|| format!("Could not read {}", path.display()))?;
+            Vocabulary::parse(&words)?
+        }
+        None => Vocabulary::builtin(&args.language)?,
+    };
 
-        // part two
-        let first_digit_from_left = re
-            .find_iter(line)
-            .next()
-            .context("Expected line to have atleast 1 match")?
-            .as_str();
-        let first_digit_from_left = parse_digit_or_str(first_digit_from_left)?;
+    // stdin is read one line at a time, so documents of any size fit
+    let input = match args.input {
+        Some(s) if s == "-" => None,
+        Some(s) => Some(s),
+        None => {
+            Some(get_puzzle_input(2023, 1, session).context("Could not retrieve puzzle input")?)
+        }
+    };
+    let reader: Box<dyn BufRead> = match &input {
+        Some(input) => Box::new(input.as_bytes()),
+        None => Box::new(io::stdin().lock()),
+    };
 
-        let first_digit_from_right = re_from_right
-            .captures_iter(line)
-            .last()
-            .context("Expected line to have atleast 1 match")?
-            .get(1)
-            .unwrap()
-            .as_str();
-        let first_digit_from_right = parse_digit_or_str(first_digit_from_right)?;
-        sum_part_two += first_digit_from_left * 10 + first_digit_from_right
+    if args.report {
+        return report(reader, &vocabulary);
     }
+    let (sum_part_one, sum_part_two) = sum_calibration_values(reader, &vocabulary)?;
     println!("part_one: {}", sum_part_one);
     println!("part_two: {}", sum_part_two);
     Ok(())
 }
 
-fn parse_digit_or_str(v: &str) -> Result<u32> {
-    u32::from_str_radix(v, 10).or_else(|_e| match v {
-        "one" => Ok(1),
-        "two" => Ok(2),
-        "three" => Ok(3),
-        "four" => Ok(4),
-        "five" => Ok(5),
-        "six" => Ok(6),
-        "seven" => Ok(7),
-        "eight" => Ok(8),
-        "nine" => Ok(9),
-        _ => Err(anyhow!("Could not parse {} into a u32", v)),
-    })
+/// Print the tokens and calibration values of every line, followed by the sums of the lines that have them
+fn report(reader: impl BufRead, vocabulary: &Vocabulary) -> Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let (mut sum_part_one, mut sum_part_two) = (0, 0);
+    let (mut flagged_part_one, mut flagged_part_two) = (0, 0);
+
+    scan_lines(reader, vocabulary, |number, line, calibration| {
+        writeln!(out, "line {}: {}", number, String::from_utf8_lossy(line))?;
+        for (part, tokens, sum, flagged) in [
+            (
+                "one",
+                calibration.digits,
+                &mut sum_part_one,
+                &mut flagged_part_one,
+            ),
+            (
+                "two",
+                calibration.words,
+                &mut sum_part_two,
+                &mut flagged_part_two,
+            ),
+        ] {
+            let Some((first, last)) = tokens else {
+                writeln!(out, "  part {}: no digit, left out of the sum", part)?;
+                *flagged += 1;
+                continue;
+            };
+            let value = first.digit * 10 + last.digit;
+            *sum += value as u64;
+            writeln!(
+                out,
+                "  part {}: {} from {} and {}",
+                part,
+                value,
+                describe(line, first),
+                describe(line, last)
+            )?;
+        }
+        Ok(())
+    })?;
+
+    writeln!(
+        out,
+        "part_one: {} ({} lines without a digit)",
+        sum_part_one, flagged_part_one
+    )?;
+    writeln!(
+        out,
+        "part_two: {} ({} lines without a digit)",
+        sum_part_two, flagged_part_two
+    )?;
+    Ok(())
+}
+
+/// The text of a token with its byte span, like "two" at 0..3
+fn describe(line: &[u8], token: Token) -> String {
+    format!(
+        "{:?} at {}..{}",
+        String::from_utf8_lossy(&line[token.start..token.end]),
+        token.start,
+        token.end
+    )
 }
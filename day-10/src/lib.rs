@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+
+pub struct Grid {
+    pub data: Vec<Vec<char>>,
+}
+
+impl Grid {
+    pub fn parse_input(input: &str) -> Result<Grid> {
+        let data = input
+            .lines()
+            .map(|line| line.chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let Some(first) = data.first() else {
+            bail!("Expected atleast one row of pipes");
+        };
+        if data.iter().any(|row| row.len() != first.len()) {
+            bail!("Every row should have the same length");
+        }
+        if let Some(ch) = data
+            .iter()
+            .flatten()
+            .find(|ch| !['|', '-', 'L', 'J', '7', 'F', '.', 'S'].contains(ch))
+        {
+            bail!("Unexpected tile: {}", ch);
+        }
+        Ok(Grid { data })
+    }
+
+    pub fn find_char(&self, search: char) -> Option<(i32, i32)> {
+        let mut position = None;
+        for y in 0..self.rows() {
+            for x in 0..self.cols() {
+                let (x, y): (i32, i32) = (x as i32, y as i32);
+                let ch = *self.get(x, y);
+                if ch == search {
+                    position = Some((x, y));
+                    break;
+                }
+            }
+        }
+        position
+    }
+
+    pub fn iter_mut(&mut self, f: impl Fn(i32, i32, &mut char)) {
+        for y in 0..self.rows() {
+            for x in 0..self.cols() {
+                let (x, y): (i32, i32) = (x as i32, y as i32);
+                f(x, y, self.get_mut(x, y));
+                // print!("{}", self.get(x,y))
+            }
+            // print!("{}", "\n")
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.data[0].len()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> &char {
+        &self.data[y as usize][x as usize]
+    }
+
+    pub fn get_mut(&mut self, x: i32, y: i32) -> &mut char {
+        &mut self.data[y as usize][x as usize]
+    }
+
+    /// Walk the loop from S until we reach S again, calling `on_step` at every pipe along the way.
+    /// Returns the positions of all pipes in the loop.
+    pub fn walk_loop(
+        &self,
+        start: (i32, i32),
+        mut on_step: impl FnMut((i32, i32)),
+    ) -> HashSet<(i32, i32)> {
+        let mut positions = HashSet::new();
+        let mut cur = find_first_connected_pipe(start, self);
+        let mut prev = start;
+
+        loop {
+            // get current pipe from the grid
+            let current = *self.get(cur.0, cur.1);
+            positions.insert(cur);
+            on_step(cur);
+            // we're done if we reached S again
+            if current == 'S' {
+                break;
+            }
+
+            // find the next pipe that is part of the loop
+            (cur, prev) = (next(current, cur, prev), cur);
+        }
+        positions
+    }
+
+    /// The tiles enclosed by the loop of `pipes`, as returned by [`Grid::walk_loop`].
+    /// Walking along a row, we are inside the loop after crossing an odd number of pipes
+    /// that connect to the row above.
+    pub fn enclosed(&self, pipes: &HashSet<(i32, i32)>) -> HashSet<(i32, i32)> {
+        let connects_up = |x: i32, y: i32| match *self.get(x, y) {
+            '|' | 'L' | 'J' => true,
+            'S' => {
+                y > 0 && pipes.contains(&(x, y - 1)) && ['|', '7', 'F'].contains(self.get(x, y - 1))
+            }
+            _ => false,
+        };
+
+        let mut enclosed = HashSet::new();
+        for y in 0..self.rows() as i32 {
+            let mut inside = false;
+            for x in 0..self.cols() as i32 {
+                if !pipes.contains(&(x, y)) {
+                    if inside {
+                        enclosed.insert((x, y));
+                    }
+                } else if connects_up(x, y) {
+                    inside = !inside;
+                }
+            }
+        }
+        enclosed
+    }
+}
+
+fn find_first_connected_pipe(s_position: (i32, i32), grid: &Grid) -> (i32, i32) {
+    let (x, y) = s_position;
+    // right
+    if x + 1 < grid.cols() as i32 && ['7', 'J', '-'].contains(grid.get(x, y)) {
+        return (x + 1, y);
+    }
+    // left
+    if x - 1 > 0 && ['F', 'L', '-'].contains(grid.get(x - 1, y)) {
+        return (x - 1, y);
+    }
+    // down
+    if y + 1 < grid.rows() as i32 && ['J', 'L', '|'].contains(grid.get(x, y + 1)) {
+        return (x, y + 1);
+    }
+    // assume it is up
+    (x, y - 1)
+}
+
+fn next(current: char, cur: (i32, i32), prev: (i32, i32)) -> (i32, i32) {
+    let (mut x, mut y) = cur;
+    let (x_prev, y_prev) = prev;
+    assert!(x != x_prev || y != y_prev);
+
+    if x < x_prev {
+        match current {
+            '-' => x -= 1,
+            'F' => y += 1,
+            'L' => y -= 1,
+            _ => panic!("wrong puzzle input"),
+        }
+    } else if x > x_prev {
+        match current {
+            '-' => x += 1,
+            '7' => y += 1,
+            'J' => y -= 1,
+            _ => panic!("wrong puzzle input"),
+        }
+    } else if y < y_prev {
+        match current {
+            '|' => y -= 1,
+            'F' => x += 1,
+            '7' => x -= 1,
+            _ => panic!("wrong puzzle input"),
+        }
+    } else if y > y_prev {
+        match current {
+            '|' => y += 1,
+            'L' => x += 1,
+            'J' => x -= 1,
+            _ => panic!("wrong puzzle input"),
+        }
+    }
+    (x, y)
+}
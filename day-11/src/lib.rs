@@ -0,0 +1,21 @@
+use anyhow::{bail, Result};
+
+/// Parse the image of the universe into rows of '.' and '#'
+pub fn parse_input(input: &str) -> Result<Vec<Vec<char>>> {
+    let universe: Vec<Vec<_>> = input.lines().map(|line| line.chars().collect()).collect();
+
+    let Some(first) = universe.first() else {
+        bail!("Expected atleast one row in the universe");
+    };
+    if universe.iter().any(|line| line.len() != first.len()) {
+        bail!("Every row of the universe should have the same length");
+    }
+    if let Some(ch) = universe
+        .iter()
+        .flatten()
+        .find(|ch| **ch != '.' && **ch != '#')
+    {
+        bail!("Unexpected character in the universe: {}", ch);
+    }
+    Ok(universe)
+}
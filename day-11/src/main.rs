@@ -1,15 +1,15 @@
-use std::env;
+use std::{env, io};
 
 use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
 use clap::Parser;
-
+use day_11::parse_input;
 
 /// Wait for it
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// The puzzle input
+    /// The puzzle input, or - to read it from stdin
     #[arg()]
     input: Option<String>,
 
@@ -21,79 +21,15 @@ struct Args {
 fn main() -> Result<()> {
     let args = Args::parse();
     let session = args.session.or_else(|| env::var("SESSION").ok());
-    let input = if let Some(s) = args.input {
-        s
-    } else {
-        get_puzzle_input(2023, 11, session).context("Could not retrieve puzzle input!")?
+    let input = match args.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        None => get_puzzle_input(2023, 11, session).context("Could not retrieve puzzle input!")?,
     };
 
-    let mut universe: Vec<Vec<_>> = input.lines().map(|line| line.chars().collect()).collect();
-
-    //
-    // expand universe horizontally
-    //
-    let empty_rows: Vec<_> = universe
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, line)| line.iter().all(|ch| ch == &'.').then_some(idx))
-        .collect();
-
-    for (row, offset_from_copies) in empty_rows.clone().into_iter().enumerate() {
-        let copy = universe[row + offset_from_copies].clone();
-        universe.insert(row + offset_from_copies, copy);
-    }
-
-    //
-    // expand universe vertically
-    //
-    let n_cols = universe.first().unwrap().len();
-    let empty_cols: Vec<_> = (0..n_cols)
-        .filter_map(|c| {
-            universe
-                .iter()
-                .map(move |line| line[c])
-                .all(|ch| ch == '.')
-                .then_some(c)
-        })
-        .collect();
+    let universe = parse_input(&input)?;
 
-    for (col, offset_from_copies) in empty_cols.clone().into_iter().enumerate() {
-        for line in universe.iter_mut() {
-            line.insert(col + offset_from_copies, '.')
-        }
-    }
-
-    //
-    // build map of galaxies and their location
-    // e.g. galaxies: (id, (col,row))
-    //
-    let n_cols = universe.first().unwrap().len();
-    let n_rows = universe.len();
-    let mut galaxies = Vec::new();
-    for row in 0..n_rows {
-        for col in 0..n_cols {
-            if universe[row][col] == '#' {
-                galaxies.push((row, col));
-            }
-        }
-    }
-
-    // compare pairs of galaxies
-    let mut distances = Vec::new();
-    for i in 0..galaxies.len() {
-        for j in i..galaxies.len() {
-            // skip 'same' pair
-            if i == j {
-                continue;
-            }
-            // compute manhatten distance between galaxies
-            let (x1, y1) = galaxies[i];
-            let (x2, y2) = galaxies[j];
-            let dist = abs(x2, x1) + abs(y2, y1);
-            distances.push(dist);
-        }
-    }
-    println!("Part one: {}", distances.iter().sum::<usize>());
+    println!("Part one: {}", sum_of_distances(&universe, 2));
 
     //
     // part two
@@ -103,45 +39,63 @@ fn main() -> Result<()> {
     // - count the times we cross an empty column or row
     // - multiply the distance by this count
     // - probably use a big number type
+    println!("Part two: {}", sum_of_distances(&universe, 1_000_000));
 
-    // parse
-    let universe: Vec<Vec<_>> = input.lines().map(|line| line.chars().collect()).collect();
+    Ok(())
+}
 
-    //
-    // array of galaxies' location
-    //
-    let n_cols = universe.first().unwrap().len();
-    let n_rows = universe.len();
+fn empty_rows(universe: &[Vec<char>]) -> Vec<usize> {
+    universe
+        .iter()
+        .enumerate()
+        .filter(|(_idx, line)| line.iter().all(|ch| ch == &'.'))
+        .map(|(idx, _line)| idx)
+        .collect()
+}
+
+fn empty_cols(universe: &[Vec<char>]) -> Vec<usize> {
+    let n_cols = universe.first().map_or(0, |line| line.len());
+    (0..n_cols)
+        .filter(|c| universe.iter().all(|line| line[*c] == '.'))
+        .collect()
+}
+
+//
+// array of galaxies' location
+// e.g. galaxies: (row,col)
+//
+fn galaxies(universe: &[Vec<char>]) -> Vec<(usize, usize)> {
     let mut galaxies = Vec::new();
-    for row in 0..n_rows {
-        for col in 0..n_cols {
-            if universe[row][col] == '#' {
-                galaxies.push((row as u128, col as u128));
+    for (row, line) in universe.iter().enumerate() {
+        for (col, ch) in line.iter().enumerate() {
+            if *ch == '#' {
+                galaxies.push((row, col));
             }
         }
     }
+    galaxies
+}
 
-    // compare pairs of galaxies
-    let mut distances = Vec::new();
-
-    let empty_rows = empty_rows
+/// Sum the distances between every pair of galaxies,
+/// where every empty row and column is `expansion` times as large.
+fn sum_of_distances(universe: &[Vec<char>], expansion: u128) -> u128 {
+    let galaxies = galaxies(universe);
+    let empty_rows = empty_rows(universe)
         .into_iter()
         .map(|x| x as u128)
         .collect::<Vec<_>>();
-    let empty_cols = empty_cols
+    let empty_cols = empty_cols(universe)
         .into_iter()
         .map(|x| x as u128)
         .collect::<Vec<_>>();
 
+    // compare pairs of galaxies
+    let mut distances = Vec::new();
     for i in 0..galaxies.len() {
-        for j in i..galaxies.len() {
-            // skip 'same' pair
-            if i == j {
-                continue;
-            }
+        for j in i + 1..galaxies.len() {
             // compute manhatten distance between galaxies
-            let (x1, y1) = galaxies[i];
-            let (x2, y2) = galaxies[j];
+            let (x1, y1) = (galaxies[i].0 as u128, galaxies[i].1 as u128);
+            let (x2, y2) = (galaxies[j].0 as u128, galaxies[j].1 as u128);
             let (x_max, x_min) = (u128::max(x1, x2), u128::min(x1, x2));
             let (y_max, y_min) = (u128::max(y1, y2), u128::min(y1, y2));
             let dist = (x_max - x_min) + (y_max - y_min);
@@ -159,15 +113,83 @@ fn main() -> Result<()> {
             }
 
             // correct for the times we crossed that huge distance
-            let dist = dist + times_crossed * 1_000_000 - times_crossed;
+            let dist = dist + times_crossed * expansion - times_crossed;
             distances.push(dist);
         }
     }
-    println!("Part two: {}", distances.iter().sum::<u128>());
-
-    Ok(())
+    distances.iter().sum()
 }
 
-fn abs(a: usize, b: usize) -> usize {
-    return if b >= a { b - a } else { a - b };
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Expand the universe by inserting a copy for every empty row and column,
+    /// and compute the distances on the expanded universe.
+    fn sum_of_distances_by_expansion(universe: &[Vec<char>], expansion: usize) -> u128 {
+        let mut universe = universe.to_vec();
+
+        //
+        // expand universe horizontally
+        //
+        for (offset_from_copies, row) in empty_rows(&universe).into_iter().enumerate() {
+            let row = row + offset_from_copies * (expansion - 1);
+            let copy = universe[row].clone();
+            for _ in 1..expansion {
+                universe.insert(row, copy.clone());
+            }
+        }
+
+        //
+        // expand universe vertically
+        //
+        for (offset_from_copies, col) in empty_cols(&universe).into_iter().enumerate() {
+            let col = col + offset_from_copies * (expansion - 1);
+            for line in universe.iter_mut() {
+                for _ in 1..expansion {
+                    line.insert(col, '.')
+                }
+            }
+        }
+
+        // compare pairs of galaxies
+        let galaxies = galaxies(&universe);
+        let mut distances = Vec::new();
+        for i in 0..galaxies.len() {
+            for j in i + 1..galaxies.len() {
+                let (x1, y1) = galaxies[i];
+                let (x2, y2) = galaxies[j];
+                distances.push((x2.abs_diff(x1) + y2.abs_diff(y1)) as u128);
+            }
+        }
+        distances.iter().sum()
+    }
+
+    /// Small rectangular universes, with galaxies placed sparsely
+    fn universe() -> impl Strategy<Value = Vec<Vec<char>>> {
+        (1..8usize, 1..8usize).prop_flat_map(|(rows, cols)| {
+            let ch = prop_oneof![3 => Just('.'), 1 => Just('#')];
+            prop::collection::vec(prop::collection::vec(ch, cols), rows)
+        })
+    }
+
+    #[test]
+    fn sample_input() {
+        let input = include_str!("sample-input.txt");
+        let universe = parse_input(input).unwrap();
+        assert_eq!(sum_of_distances(&universe, 2), 374);
+        assert_eq!(sum_of_distances(&universe, 10), 1030);
+        assert_eq!(sum_of_distances(&universe, 100), 8410);
+    }
+
+    proptest! {
+        #[test]
+        fn counting_crossings_matches_expansion(universe in universe(), expansion in 1..5usize) {
+            prop_assert_eq!(
+                sum_of_distances(&universe, expansion as u128),
+                sum_of_distances_by_expansion(&universe, expansion)
+            );
+        }
+    }
 }
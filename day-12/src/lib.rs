@@ -0,0 +1,166 @@
+use std::{collections::HashMap, num::ParseIntError};
+
+use anyhow::{bail, Context, Result};
+
+pub fn parse_input(input: &str) -> Result<Vec<Line>> {
+    input.lines().map(Line::parse).collect()
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub struct Line {
+    pub springs: Vec<char>,
+    pub groups: Vec<usize>,
+}
+
+impl Line {
+    pub fn new(springs: Vec<char>, groups: Vec<usize>) -> Self {
+        Self { springs, groups }
+    }
+
+    pub fn parse(line: &str) -> Result<Line> {
+        let mut split = line.split_ascii_whitespace();
+        let springs: Vec<char> = split
+            .next()
+            .context("Expected a row of springs")?
+            .chars()
+            .collect();
+        let groups: Vec<usize> = split
+            .next()
+            .context("Expected a list of damaged groups")?
+            .split(',')
+            .map(|v| v.parse())
+            .collect::<Result<Vec<_>, ParseIntError>>()?;
+
+        if let Some(ch) = springs.iter().find(|ch| !['.', '#', '?'].contains(ch)) {
+            bail!("Unexpected spring: {}", ch);
+        }
+        if groups.contains(&0) {
+            bail!("Groups of damaged springs should not be empty");
+        }
+        Ok(Line::new(springs, groups))
+    }
+
+    pub fn expand(&self) -> Line {
+        let springs = self
+            .springs
+            .iter()
+            .cloned()
+            .chain(['?'])
+            .cycle()
+            .take(self.springs.len() * 5 + 4)
+            .collect();
+        let groups = self
+            .groups
+            .iter()
+            .cloned()
+            .cycle()
+            .take(self.groups.len() * 5)
+            .collect();
+        Line::new(springs, groups)
+    }
+}
+
+pub fn possible_solutions(map: &mut HashMap<Line, usize>, line: &Line) -> usize {
+    if let Some(&v) = map.get(line) {
+        return v;
+    }
+    if line.groups.is_empty() {
+        let v = match line.springs.contains(&'#') {
+            true => 0,
+            false => 1,
+        };
+        map.insert(line.clone(), v);
+        return v;
+    }
+    if line.springs.len() < line.groups.iter().sum::<usize>() + line.groups.len() - 1 {
+        map.insert(line.clone(), 0);
+        return 0;
+    }
+    if line.springs[0] == '.' {
+        let s = possible_solutions(
+            map,
+            &Line::new(line.springs[1..].to_vec(), line.groups.clone()),
+        );
+        map.insert(line.clone(), s);
+        return s;
+    }
+
+    let mut solutions = 0;
+    let cur = line.groups[0];
+    let all_non_operational = line.springs[0..cur].iter().all(|c| *c != '.');
+    let end = (cur + 1).min(line.springs.len());
+    if all_non_operational
+        && ((line.springs.len() > cur && line.springs[cur] != '#') || line.springs.len() <= cur)
+    {
+        solutions = possible_solutions(
+            map,
+            &Line::new(line.springs[end..].to_vec(), line.groups[1..].to_vec()),
+        );
+    }
+
+    if line.springs[0] == '?' {
+        solutions += possible_solutions(
+            map,
+            &Line::new(line.springs[1..].to_vec(), line.groups.clone()),
+        );
+    }
+
+    map.insert(line.clone(), solutions);
+    solutions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Try every assignment of operational and damaged springs to the unknown springs,
+    /// and count the assignments whose contiguous damaged groups match.
+    fn possible_solutions_by_enumeration(line: &Line) -> usize {
+        let unknown: Vec<_> = (0..line.springs.len())
+            .filter(|i| line.springs[*i] == '?')
+            .collect();
+
+        let mut solutions = 0;
+        for assignment in 0..(1usize << unknown.len()) {
+            let mut springs = line.springs.clone();
+            for (bit, i) in unknown.iter().enumerate() {
+                springs[*i] = if assignment & (1 << bit) != 0 {
+                    '#'
+                } else {
+                    '.'
+                };
+            }
+            let groups: Vec<usize> = springs
+                .split(|c| *c == '.')
+                .map(|group| group.len())
+                .filter(|len| *len > 0)
+                .collect();
+            if groups == line.groups {
+                solutions += 1;
+            }
+        }
+        solutions
+    }
+
+    /// Short rows of springs, with groups that may or may not fit
+    fn line() -> impl Strategy<Value = Line> {
+        let spring = prop_oneof![Just('.'), Just('#'), Just('?')];
+        (
+            prop::collection::vec(spring, 0..12),
+            prop::collection::vec(1..5usize, 0..4),
+        )
+            .prop_map(|(springs, groups)| Line::new(springs, groups))
+    }
+
+    proptest! {
+        #[test]
+        fn memoized_matches_enumeration(line in line()) {
+            let mut memo = HashMap::new();
+            prop_assert_eq!(
+                possible_solutions(&mut memo, &line),
+                possible_solutions_by_enumeration(&line)
+            );
+        }
+    }
+}
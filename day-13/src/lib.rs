@@ -0,0 +1,106 @@
+use anyhow::{bail, Result};
+
+pub fn parse_input(input: &str) -> Result<Vec<Pattern>> {
+    input.split("\n\n").map(Pattern::parse_pattern).collect()
+}
+
+/// A record is a line or column of a pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Record(String);
+
+/// A Pattern has two representations of the same data:
+/// rows are the lines of a pattern
+/// cols are the columns of a pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    rows: Vec<Record>,
+    cols: Vec<Record>,
+}
+
+impl Pattern {
+    pub fn parse_pattern(pattern: &str) -> Result<Pattern> {
+        let Some(first) = pattern.lines().next().filter(|l| !l.is_empty()) else {
+            bail!("Expected a pattern with atleast one row");
+        };
+        if pattern.lines().any(|l| l.len() != first.len()) {
+            bail!("Every row of the pattern should have the same length");
+        }
+        if let Some(ch) = pattern.chars().find(|ch| !['.', '#', '\n'].contains(ch)) {
+            bail!("Unexpected character in pattern: {}", ch);
+        }
+        Ok(Pattern {
+            rows: Self::parse_rows(pattern),
+            cols: Self::parse_columns(pattern),
+        })
+    }
+
+    fn parse_rows(pattern: &str) -> Vec<Record> {
+        pattern
+            .lines()
+            .map(str::chars)
+            .map(|chars| Record(chars.collect()))
+            .collect()
+    }
+
+    fn parse_columns(pattern: &str) -> Vec<Record> {
+        let n = pattern.lines().next().unwrap().len();
+        let mut v = vec![];
+        for col in 0..n {
+            let data = pattern
+                .lines()
+                .map(|l| l.chars().nth(col).unwrap())
+                .collect();
+            v.push(Record(data));
+        }
+        v
+    }
+
+    fn find_ref_part_one(v: &[Record]) -> Option<usize> {
+        assert!(v.len() > 1);
+        (1..v.len()).find(|i| {
+            let left = v[0..*i].iter().rev();
+            let right = v[*i..v.len()].iter();
+            left.zip(right).all(|(a, b)| a == b)
+        })
+    }
+
+    fn find_ref_part_two(v: &[Record]) -> Option<usize> {
+        assert!(v.len() > 1);
+
+        (1..v.len()).find(|i| {
+            let left = v[0..*i].iter().rev();
+            let right = v[*i..v.len()].iter();
+
+            left.zip(right)
+                .map(|(a, b)| a.count_differences(b))
+                .sum::<usize>()
+                == 1
+        })
+    }
+
+    /// Find the line of reflection
+    /// Returns (x,true) when the reflection is vertical
+    pub fn find_reflection(&self) -> (usize, bool) {
+        let horizontal = Self::find_ref_part_one(&self.rows).map(|u| (u, false));
+        let vertical = Self::find_ref_part_one(&self.cols).map(|u| (u, true));
+        horizontal.or(vertical).unwrap()
+    }
+
+    /// Find the line of reflection
+    /// Returns (x,true) when the reflection is vertical
+    pub fn find_reflection_part_two(&self) -> (usize, bool) {
+        let horizontal = Self::find_ref_part_two(&self.rows).map(|u| (u, false));
+        let vertical = Self::find_ref_part_two(&self.cols).map(|u| (u, true));
+        horizontal.or(vertical).unwrap()
+    }
+}
+
+impl Record {
+    fn count_differences(&self, other: &Record) -> usize {
+        self.0
+            .chars()
+            .zip(other.0.chars())
+            .map(|(a, b)| if a != b { 1 } else { 0 })
+            .sum()
+    }
+}
@@ -0,0 +1,189 @@
+use std::fmt::Display;
+
+use anyhow::{bail, Context, Result};
+
+// a single spot in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Record {
+    // A round rock
+    Round,
+    // A cubic rock
+    Cubic,
+    // An empty spot
+    Empty,
+}
+
+impl Record {
+    fn from_char(v: char) -> Self {
+        match v {
+            'O' => Record::Round,
+            '#' => Record::Cubic,
+            _ => Record::Empty,
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Record::Round => 'O',
+                Record::Cubic => '#',
+                Record::Empty => '.',
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Platform {
+    pub grid: Vec<Vec<Record>>,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+impl Platform {
+    pub fn parse_platform(input: &str) -> Result<Self> {
+        if let Some(ch) = input.chars().find(|ch| !['O', '#', '.', '\n'].contains(ch)) {
+            bail!("Unexpected character on the platform: {}", ch);
+        }
+        let grid: Vec<Vec<_>> = input
+            .lines()
+            .map(|l| l.chars().map(Record::from_char).collect())
+            .collect();
+        let cols = grid.first().context("Expected atleast one row")?.len();
+        let rows = grid.len();
+        if grid.iter().any(|row| row.len() != cols) {
+            bail!("Every row of the platform should have the same length");
+        }
+        Ok(Self { grid, rows, cols })
+    }
+
+    fn swap(&mut self, (y_a, x_a): (usize, usize), (y_b, x_b): (usize, usize)) {
+        let temp = self.grid[y_a][x_a];
+        self.grid[y_a][x_a] = self.grid[y_b][x_b];
+        self.grid[y_b][x_b] = temp;
+    }
+
+    pub fn tilt_north(&mut self) {
+        for y in 0..self.cols {
+            for x in 0..self.rows {
+                if self.grid[y][x] != Record::Round {
+                    continue;
+                }
+
+                // for every round rock, move it up as much as possible
+                // by swapping with an empty spot above it
+                let mut y_prev = y;
+                for y_new in (0..y).rev() {
+                    if self.grid[y_new][x] != Record::Empty {
+                        break;
+                    }
+                    self.swap((y_new, x), (y_prev, x));
+                    y_prev = y_new;
+                }
+            }
+        }
+    }
+
+    pub fn tilt_south(&mut self) {
+        for y in (0..self.cols).rev() {
+            for x in 0..self.rows {
+                if self.grid[y][x] != Record::Round {
+                    continue;
+                }
+
+                let mut y_prev = y;
+                for y_new in y + 1..self.cols {
+                    if self.grid[y_new][x] != Record::Empty {
+                        break;
+                    }
+                    self.swap((y_new, x), (y_prev, x));
+                    y_prev = y_new;
+                }
+            }
+        }
+    }
+
+    pub fn tilt_west(&mut self) {
+        for x in 0..self.rows {
+            for y in 0..self.cols {
+                if self.grid[y][x] != Record::Round {
+                    continue;
+                }
+
+                let mut x_prev = x;
+                for x_new in (0..x).rev() {
+                    if self.grid[y][x_new] != Record::Empty {
+                        break;
+                    }
+                    self.swap((y, x_new), (y, x_prev));
+                    x_prev = x_new;
+                }
+            }
+        }
+    }
+
+    pub fn tilt_east(&mut self) {
+        for x in (0..self.rows).rev() {
+            for y in 0..self.cols {
+                if self.grid[y][x] != Record::Round {
+                    continue;
+                }
+
+                let mut x_prev = x;
+                for x_new in x + 1..self.rows {
+                    if self.grid[y][x_new] != Record::Empty {
+                        break;
+                    }
+                    self.swap((y, x_new), (y, x_prev));
+                    x_prev = x_new;
+                }
+            }
+        }
+    }
+
+    /// A single spin cycle tilts north, west, south and east,
+    /// `on_tilt` is called after every tilt
+    pub fn spin_cycle(&mut self, mut on_tilt: impl FnMut(&Self)) {
+        for tilt in [
+            Self::tilt_north,
+            Self::tilt_west,
+            Self::tilt_south,
+            Self::tilt_east,
+        ] {
+            tilt(self);
+            on_tilt(self);
+        }
+    }
+
+    pub fn compute_total_load(&self) -> usize {
+        let mut load = 0;
+
+        for y in 0..self.cols {
+            let load_factor = self.cols - y;
+            for x in 0..self.rows {
+                if self.grid[y][x] != Record::Round {
+                    continue;
+                }
+                load += load_factor;
+            }
+        }
+        load
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        for y in 0..self.cols {
+            for x in 0..self.rows {
+                write!(f, "{}", self.grid[y][x])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anyhow::{bail, Context, Result};
+
+/// A single step of the initialization sequence
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// symbol: =
+    Insert(Lens),
+    /// symbol: -
+    Remove(String),
+}
+
+impl Step {
+    pub fn parse_step(seq: &str) -> Result<Step> {
+        // instruction has = sign
+        if let Some((label, focal_length)) = seq.split_once('=') {
+            let focal_length = focal_length
+                .parse()
+                .with_context(|| format!("Invalid focal length in step: {}", seq))?;
+            if !(1..=9).contains(&focal_length) {
+                bail!(
+                    "Focal length should be between 1 and 9, got: {}",
+                    focal_length
+                );
+            }
+            return Ok(Step::Insert(Lens {
+                label: label.into(),
+                focal_length,
+            }));
+        }
+
+        // instruction has - sign
+        if let Some(label) = seq.strip_suffix('-') {
+            return Ok(Step::Remove(label.into()));
+        }
+        bail!("Expected step to contain an operation: {}", seq)
+    }
+}
+
+/// Parse the initialization sequence, ignoring newlines
+pub fn parse_input(input: &str) -> Result<Vec<Step>> {
+    let without_newlines = input.chars().filter(|ch| *ch != '\n').collect::<String>();
+    without_newlines.split(',').map(Step::parse_step).collect()
+}
+
+pub fn holiday_ascii_string_helper_algorithm(v: &str) -> u32 {
+    let mut result = 0;
+    for ch in v.chars() {
+        let code_point = ch as u32;
+        result = ((result + code_point) * 17) % 256
+    }
+    result
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lens {
+    pub label: String,
+    pub focal_length: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Box {
+    pub lenses: Vec<Lens>,
+}
+
+impl Box {
+    pub fn new() -> Self {
+        Self { lenses: Vec::new() }
+    }
+}
@@ -0,0 +1,234 @@
+use std::{collections::HashMap, fmt::Display};
+
+use anyhow::{bail, Context, Result};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    /// symbol: .
+    Empty,
+    /// symbol: /
+    LeftMirror,
+    /// symbol: \
+    RightMirror,
+    /// symbol: -
+    HorizontalSplitter,
+    /// symbol: |
+    VerticalSplitter,
+}
+
+impl Tile {
+    fn from_char(v: char) -> Result<Self> {
+        use Tile::*;
+        match v {
+            '/' => Ok(LeftMirror),
+            '\\' => Ok(RightMirror),
+            '-' => Ok(HorizontalSplitter),
+            '|' => Ok(VerticalSplitter),
+            '.' => Ok(Empty),
+            _ => bail!("Unexpected tile: {}", v),
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Tile::Empty => '.',
+                Tile::LeftMirror => '/',
+                Tile::RightMirror => '\\',
+                Tile::HorizontalSplitter => '-',
+                Tile::VerticalSplitter => '|',
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    pub rows: usize,
+    pub cols: usize,
+    pub tiles: Vec<Vec<Tile>>,
+}
+
+impl Grid {
+    pub fn parse_grid(input: &str) -> Result<Self> {
+        let data = input
+            .lines()
+            .map(|l| l.chars().map(Tile::from_char).collect::<Result<Vec<_>>>())
+            .collect::<Result<Vec<_>>>()?;
+        let rows = data.len();
+        let cols = data
+            .first()
+            .context("Expected atleast one row of tiles")?
+            .len();
+        if cols == 0 || data.iter().any(|row| row.len() != cols) {
+            bail!("Every row should have the same, non-zero, number of tiles");
+        }
+        Ok(Self {
+            rows,
+            cols,
+            tiles: data,
+        })
+    }
+
+    pub fn clamp(&self, (x, y): (usize, usize), beam_dir: BeamDirection) -> Option<(usize, usize)> {
+        assert!(x < self.cols);
+        assert!(y < self.rows);
+
+        match beam_dir {
+            BeamDirection::North => (y > 0).then(|| (x, y - 1)),
+            BeamDirection::East => (x + 1 < self.cols).then(|| (x + 1, y)),
+            BeamDirection::West => (x > 0).then(|| (x - 1, y)),
+            BeamDirection::South => (y + 1 < self.rows).then(|| (x, y + 1)),
+        }
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        for y in 0..self.cols {
+            for x in 0..self.rows {
+                write!(f, "{}", self.tiles[y][x])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BeamDirection {
+    North,
+    East,
+    West,
+    South,
+}
+
+impl BeamDirection {
+    /// symbol: /
+    pub fn mirror_beam_left(self) -> BeamDirection {
+        use BeamDirection::*;
+        match self {
+            North => East,
+            East => North,
+            West => South,
+            South => West,
+        }
+    }
+
+    /// symbol: \
+    pub fn mirror_beam_right(self) -> BeamDirection {
+        use BeamDirection::*;
+        match self {
+            North => West,
+            East => South,
+            West => North,
+            South => East,
+        }
+    }
+
+    //// symbol: -
+    pub fn split_beam_horizontally(self) -> Vec<BeamDirection> {
+        use BeamDirection::*;
+        match self {
+            North => vec![West, East],
+            East => vec![self],
+            West => vec![self],
+            South => vec![West, East],
+        }
+    }
+
+    //// symbol: |
+    pub fn split_beam_vertically(self) -> Vec<BeamDirection> {
+        use BeamDirection::*;
+        match self {
+            North => vec![self],
+            East => vec![North, South],
+            West => vec![North, South],
+            South => vec![self],
+        }
+    }
+}
+
+/// Follow a beam into `pos`, recording every direction a beam passed through a tile in `positions`.
+/// `on_step` is called whenever a beam enters a tile in a direction it hadn't before.
+pub fn mark_tiles(
+    grid: &Grid,
+    pos: Option<(usize, usize)>,
+    beam_dir: BeamDirection,
+    positions: &mut HashMap<(usize, usize), Vec<BeamDirection>>,
+    on_step: &mut impl FnMut((usize, usize), BeamDirection),
+) {
+    // Invoked mark_tiles with a position outside the grid, simply return
+    let Some(pos) = pos else {
+        return;
+    };
+
+    // Direction for this tile/position that is already computed
+    let seen_directions = positions.entry(pos).or_default();
+
+    // we've already been on this tile, for this incoming beam direction
+    if seen_directions.contains(&beam_dir) {
+        return;
+    } else {
+        seen_directions.push(beam_dir);
+    }
+    on_step(pos, beam_dir);
+
+    // match on this tile
+    match grid.tiles[pos.1][pos.0] {
+        Tile::Empty => mark_tiles(
+            grid,
+            grid.clamp(pos, beam_dir),
+            beam_dir,
+            positions,
+            on_step,
+        ),
+        Tile::LeftMirror => {
+            let beam_dir = beam_dir.mirror_beam_left();
+            mark_tiles(
+                grid,
+                grid.clamp(pos, beam_dir),
+                beam_dir,
+                positions,
+                on_step,
+            );
+        }
+        Tile::RightMirror => {
+            let beam_dir = beam_dir.mirror_beam_right();
+            mark_tiles(
+                grid,
+                grid.clamp(pos, beam_dir),
+                beam_dir,
+                positions,
+                on_step,
+            );
+        }
+        Tile::HorizontalSplitter => {
+            for beam_dir in beam_dir.split_beam_horizontally() {
+                mark_tiles(
+                    grid,
+                    grid.clamp(pos, beam_dir),
+                    beam_dir,
+                    positions,
+                    on_step,
+                );
+            }
+        }
+        Tile::VerticalSplitter => {
+            for beam_dir in beam_dir.split_beam_vertically() {
+                mark_tiles(
+                    grid,
+                    grid.clamp(pos, beam_dir),
+                    beam_dir,
+                    positions,
+                    on_step,
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use anyhow::{bail, Context, Result};
+
+#[derive(Debug, Clone)]
+pub struct Grid {
+    pub data: Vec<Vec<u32>>,
+    pub cols: usize,
+    pub rows: usize,
+}
+
+impl Grid {
+    pub fn parse_grid(input: &str) -> Result<Self> {
+        let data = input
+            .lines()
+            .map(|l| {
+                l.chars()
+                    .map(|ch| {
+                        ch.to_digit(10)
+                            .with_context(|| format!("Expected a digit, got: {}", ch))
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let cols = data.len();
+        let rows = data.first().context("Expected atleast one row")?.len();
+
+        // sanity check: the grid is rectangular
+        if rows == 0 || data.iter().any(|row| row.len() != rows) {
+            bail!("Every row should have the same, non-zero, length");
+        }
+
+        // number of cols, number of rows should fit in a isize
+        Ok(Self { data, cols, rows })
+    }
+
+    /// Move the current position `(x,y)` along Direction `dir`
+    /// Returns `Some(x_new,y_new)` if the move is valid; i.e. the new position lies in the grid.
+    pub fn move_along_dir(&self, (x, y): (usize, usize), dir: Direction) -> Option<Position> {
+        use Direction::*;
+        assert!(x < self.cols);
+        assert!(y < self.rows);
+
+        let v = match dir {
+            Up => (y > 0).then(|| (x, y - 1)),
+            Right => (x + 1 < self.cols).then(|| (x + 1, y)),
+            Left => (x > 0).then(|| (x - 1, y)),
+            Down => (y + 1 < self.rows).then(|| (x, y + 1)),
+        };
+        v.map(|v| Position::new(v, dir))
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> u32 {
+        self.data[y][x]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub fn rotate_clockwise(&self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Right,
+            Left => Up,
+            Down => Left,
+            Right => Down,
+        }
+    }
+
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        use Direction::*;
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+    pub dir: Direction,
+}
+
+impl Position {
+    pub fn new((x, y): (usize, usize), dir: Direction) -> Self {
+        Self { x, y, dir }
+    }
+
+    pub fn loc(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+}
+
+/// Something the search did, for anyone who wants to watch it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// A path to a position was queued, with the steps it moved in a straight line and its heat loss
+    Queued(Position, usize, u32),
+    /// The best path to a position was taken from the queue, with the steps it moved in a straight line and its heat loss
+    Visited(Position, usize, u32),
+}
+
+/// Find the least heat loss from the top left to the bottom right block,
+/// for a crucible that moves atleast `min_steps` before it can turn and atmost `max_steps` in a single direction.
+/// `on_step` is called whenever the search queues or visits a path.
+pub fn dijkstra(
+    grid: &Grid,
+    min_steps: usize,
+    max_steps: usize,
+    mut on_step: impl FnMut(Event),
+) -> u32 {
+    // initial state
+    let mut queue = BinaryHeap::from(vec![
+        State::new(0, Position::new((0, 0), Direction::Down), 0),
+        State::new(0, Position::new((0, 0), Direction::Right), 0),
+    ]);
+
+    // set of visited nodes
+    let mut visited: HashMap<(Position, usize), u32> = HashMap::new();
+
+    let mut dist = vec![u32::MAX; grid.cols * grid.rows];
+    dist[0] = 0;
+
+    while let Some(State {
+        cost,
+        position,
+        steps,
+    }) = queue.pop()
+    {
+        // if we've already visited this node, we visited this node with a better heat value.
+        // So skip this one.
+        if let Some(old_heat) = visited.insert((position, steps), cost) {
+            // sanity check: this really is true
+            assert!(old_heat <= cost);
+            continue;
+        }
+        on_step(Event::Visited(position, steps, cost));
+
+        let mut next_states = vec![];
+        // we can only turn after moving min_steps, and move clock-wise or counter-clock-wise
+        if steps >= min_steps {
+            for dir in [
+                position.dir.rotate_clockwise(),
+                position.dir.rotate_counter_clockwise(),
+            ] {
+                if let Some(turned) = grid.move_along_dir(position.loc(), dir) {
+                    next_states.push(State::new(cost + grid.get(turned.loc()), turned, 1));
+                }
+            }
+        }
+
+        // we can only move forward if we haven't moved max_steps in this direction already
+        if let Some(forward) = grid
+            .move_along_dir(position.loc(), position.dir)
+            .filter(|_| steps < max_steps)
+        {
+            next_states.push(State::new(
+                cost + grid.get(forward.loc()),
+                forward,
+                steps + 1,
+            ));
+        }
+
+        for next in next_states {
+            // if this next state is the best option
+            let (x, y) = next.position.loc();
+            if next.cost < dist[y * grid.rows + x] {
+                dist[y * grid.rows + x] = next.cost;
+            }
+            on_step(Event::Queued(next.position, next.steps, next.cost));
+            queue.push(next);
+        }
+    }
+
+    dist[(grid.cols - 1) * grid.cols + grid.rows - 1]
+}
+
+/// Find a path with the least heat loss, like [`dijkstra`].
+/// Returns the least heat loss and the positions along the path, starting at the top left block.
+pub fn least_heat_path(grid: &Grid, min_steps: usize, max_steps: usize) -> (u32, Vec<Position>) {
+    // every path is queued right after the path it extends is visited,
+    // so remember the cheapest way into every (position, steps)
+    let mut best = HashMap::new();
+    let mut last_visited = None;
+    let mut end = None;
+    let heat_loss = dijkstra(grid, min_steps, max_steps, |event| match event {
+        Event::Visited(position, steps, cost) => {
+            best.entry((position, steps)).or_insert((cost, None));
+            last_visited = Some((position, steps));
+            if position.loc() == (grid.cols - 1, grid.rows - 1) {
+                end = end
+                    .filter(|&(_, least)| least <= cost)
+                    .or(Some(((position, steps), cost)));
+            }
+        }
+        Event::Queued(position, steps, cost) => {
+            let entry = best.entry((position, steps)).or_insert((u32::MAX, None));
+            if cost < entry.0 {
+                *entry = (cost, last_visited);
+            }
+        }
+    });
+
+    let mut path = vec![];
+    let mut current = end.map(|(state, _)| state);
+    while let Some(state) = current {
+        path.push(state.0);
+        current = best[&state].1;
+    }
+    path.reverse();
+    (heat_loss, path)
+}
+
+/// Represents a state of a single path the Crucible can walk
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct State {
+    /// The accumulated heat cost of the path
+    cost: u32,
+    /// The current position
+    position: Position,
+    /// The current amount of steps in the same direction
+    steps: usize,
+}
+
+impl State {
+    fn new(cost: u32, position: Position, steps: usize) -> Self {
+        Self {
+            cost,
+            position,
+            steps,
+        }
+    }
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .cmp(&self.cost)
+            .then_with(|| self.position.cmp(&other.position))
+            .then_with(|| self.steps.cmp(&other.steps))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
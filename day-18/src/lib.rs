@@ -0,0 +1,55 @@
+use ::phf::{phf_map, Map};
+use anyhow::{bail, Context, Result};
+
+static MAP: Map<&'static str, (i64, i64)> = phf_map! {
+    "R" => (1,0),
+    "D" => (0,1),
+    "L" => (-1,0),
+    "U" => (0,-1),
+
+    "0" => (1,0),
+    "1" => (0,1),
+    "2" => (-1,0),
+    "3" => (0,-1),
+};
+
+/// The dig plan, as (x, y, n) steps: move n times in direction (x, y)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigPlan {
+    /// The steps given by the direction and the number of meters
+    pub part_one: Vec<(i64, i64, i64)>,
+    /// The steps hidden in the color codes
+    pub part_two: Vec<(i64, i64, i64)>,
+}
+
+pub fn parse_input(input: &str) -> Result<DigPlan> {
+    let mut part_one = vec![];
+    let mut part_two = vec![];
+
+    for l in input.lines() {
+        let mut it = l.split_ascii_whitespace();
+        let (x, y) = direction(it.next().context("Expected a direction")?)?;
+        let n: u32 = it.next().context("Expected a number of meters")?.parse()?;
+        part_one.push((x, y, n as i64));
+
+        // color looks like (#70c710)
+        let color = it.next().context("Expected a color code")?;
+        let Some(color) = color
+            .strip_prefix("(#")
+            .and_then(|c| c.strip_suffix(')'))
+            .filter(|c| c.len() == 6 && c.is_ascii())
+        else {
+            bail!("Invalid color code: {}", color);
+        };
+        let n = i64::from_str_radix(&color[0..5], 16)?;
+        let (x, y) = direction(&color[5..6])?;
+        part_two.push((x, y, n));
+    }
+    Ok(DigPlan { part_one, part_two })
+}
+
+fn direction(v: &str) -> Result<(i64, i64)> {
+    MAP.get(v)
+        .copied()
+        .with_context(|| format!("Invalid direction: {}", v))
+}
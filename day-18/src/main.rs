@@ -1,15 +1,15 @@
-use std::env;
+use std::{env, io};
 
-use ::phf::{phf_map, Map};
 use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
 use clap::Parser;
+use day_18::parse_input;
 
 /// Lavaduct Lagoon
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// The puzzle input
+    /// The puzzle input, or - to read it from stdin
     #[arg()]
     input: Option<String>,
 
@@ -18,54 +18,22 @@ struct Args {
     session: Option<String>,
 }
 
-static MAP: Map<&'static str, (i64, i64)> = phf_map! {
-    "R" => (1,0),
-    "D" => (0,1),
-    "L" => (-1,0),
-    "U" => (0,-1),
-
-    "0" => (1,0),
-    "1" => (0,1),
-    "2" => (-1,0),
-    "3" => (0,-1),
-};
-
 fn main() -> Result<()> {
     let args = Args::parse();
     let session = args.session.or_else(|| env::var("SESSION").ok());
 
-    let input = if let Some(s) = args.input {
-        s
-    } else {
-        get_puzzle_input(2023, 18, session).context("Could not retrieve puzzle input")?
+    let input = match args.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        None => get_puzzle_input(2023, 18, session).context("Could not retrieve puzzle input")?,
     };
 
-    println!("{}", part_one(&input));
-    println!("{}", part_two(&input));
+    let plan = parse_input(&input)?;
+    println!("{}", f(plan.part_one.into_iter()));
+    println!("{}", f(plan.part_two.into_iter()));
     Ok(())
 }
 
-fn part_one(input: &str) -> i64 {
-    let iter = input.lines().map(|l| {
-        let mut it = l.split_ascii_whitespace();
-        let (x, y) = MAP.get(it.next().unwrap()).unwrap();
-        let n = i64::from_str_radix(it.next().unwrap(), 10).unwrap();
-        (*x, *y, n)
-    });
-    f(iter)
-}
-
-fn part_two(input: &str) -> i64 {
-    let iter = input.lines().map(|l| {
-        let color = l.split_ascii_whitespace().skip(2).next().unwrap();
-
-        let n = i64::from_str_radix(&color[2..7], 16).unwrap();
-        let (x, y) = MAP.get(&color[7..8]).unwrap();
-        (*x, *y, n)
-    });
-    f(iter)
-}
-
 fn f(steps: impl Iterator<Item = (i64, i64, i64)>) -> i64 {
     // first point is at (0,0)
     let (mut pos_x, mut pos_y) = (0, 0);
@@ -0,0 +1,202 @@
+use std::{collections::HashMap, str::FromStr};
+
+use anyhow::{bail, Context, Result};
+use phf::{phf_map, Map};
+use regex::Regex;
+use Op::*;
+
+static MAP: Map<&'static str, usize> = phf_map! {
+    "x" => 0,
+    "m" => 1,
+    "a" => 2,
+    "s" => 3,
+};
+
+/// Parse the workflows by name, and the parts to sort
+pub fn parse_input(input: &str) -> Result<(HashMap<String, WorkFlow>, Vec<Part>)> {
+    let (flows, parts) = input
+        .split_once("\n\n")
+        .context("expected workflows and parts separated by an empty line")?;
+
+    let re = Regex::new(r"^(\w+)\{(.*)\}$").unwrap();
+    let flows: HashMap<String, WorkFlow> = flows
+        .lines()
+        .map(|l| WorkFlow::from_str(l, &re))
+        .collect::<Result<_>>()?;
+    let parts: Vec<Part> = parts.lines().map(str::parse).collect::<Result<_>>()?;
+
+    // every workflow we send parts to should exist
+    if !flows.contains_key("in") {
+        bail!("expected a workflow named in");
+    }
+    for rule in flows.values().flat_map(|flow| flow.rules.iter()) {
+        let dest = rule.destination.as_str();
+        if dest != "A" && dest != "R" && !flows.contains_key(dest) {
+            bail!("expected a workflow named {dest}");
+        }
+    }
+
+    Ok((flows, parts))
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkFlow {
+    pub rules: Vec<Rule>,
+}
+
+impl WorkFlow {
+    fn from_str(v: &str, re: &Regex) -> Result<(String, Self)> {
+        let (_m, [id, rules]) = re
+            .captures(v)
+            .map(|c| c.extract())
+            .with_context(|| format!("expected a workflow, got {v}"))?;
+        let rules: Vec<Rule> = rules.split(',').map(str::parse).collect::<Result<_>>()?;
+        if rules.last().is_some_and(|rule| rule.op != None) {
+            bail!("expected the last rule of workflow {id} to have no condition");
+        }
+        Ok((id.into(), Self { rules }))
+    }
+}
+
+/// Represents a single rule in a workflow
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub prop: String,
+    pub op: Op,
+    pub value: i32,
+    pub destination: String,
+}
+
+impl FromStr for Rule {
+    type Err = anyhow::Error;
+
+    fn from_str(v: &str) -> Result<Self> {
+        // no op
+        let Some((condition, destination)) = v.split_once(':') else {
+            return Ok(Self {
+                prop: ".".into(),
+                op: None,
+                value: 0,
+                destination: v.into(),
+            });
+        };
+
+        let mut chars = condition.chars();
+        let prop = chars
+            .next()
+            .context("expected a property in rule")?
+            .to_string();
+        if !MAP.contains_key(&prop) {
+            bail!("expected one of x, m, a or s, got {prop}");
+        }
+        let op = match chars.next() {
+            Some('>') => Gt,
+            Some('<') => Lt,
+            c => bail!("expected < or >, got {c:?}"),
+        };
+        let value = chars.as_str().parse()?;
+        if !(1..=4000).contains(&value) {
+            bail!("expected a value between 1 and 4000, got {value}");
+        }
+        Ok(Self {
+            prop,
+            op,
+            value,
+            destination: destination.into(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Lt,
+    Gt,
+    None,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Part {
+    pub xmas: [i32; 4],
+}
+
+impl FromStr for Part {
+    type Err = anyhow::Error;
+
+    fn from_str(v: &str) -> Result<Self> {
+        let mut xmas = [0; 4];
+        // remove '{'  and '}' delimiters
+        let v = v
+            .strip_prefix('{')
+            .and_then(|v| v.strip_suffix('}'))
+            .with_context(|| format!("expected a part between braces, got {v}"))?;
+        let ratings: Vec<_> = v.split(',').collect();
+        if ratings.len() != 4 {
+            bail!("expected a part with 4 ratings, got {v}");
+        }
+        for rating in ratings {
+            let (prop, value) = rating
+                .split_once('=')
+                .with_context(|| format!("expected a rating, got {rating}"))?;
+            let index = MAP
+                .get(prop)
+                .with_context(|| format!("expected one of x, m, a or s, got {prop}"))?;
+            let value = value.parse()?;
+            if !(1..=4000).contains(&value) {
+                bail!("expected a rating between 1 and 4000, got {value}");
+            }
+            xmas[*index] = value;
+        }
+        Ok(Self { xmas })
+    }
+}
+
+impl Part {
+    pub fn get(&self, rule: &Rule) -> i32 {
+        self.xmas[*MAP.get(&rule.prop).unwrap()]
+    }
+
+    pub fn rating(&self) -> i32 {
+        self.xmas.iter().sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartRange {
+    pub xmas: [(i32, i32); 4],
+}
+
+impl Default for PartRange {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartRange {
+    pub fn new() -> Self {
+        Self {
+            xmas: [(1, 4001); 4],
+        }
+    }
+
+    pub fn get(&self, rule: &Rule) -> (i32, i32) {
+        self.xmas[*MAP.get(&rule.prop).unwrap()]
+    }
+
+    pub fn get_mut(&mut self, rule: &Rule) -> &mut (i32, i32) {
+        &mut self.xmas[*MAP.get(&rule.prop).unwrap()]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        // left inclusive, right exclusive
+        // so 1..1 = empty
+        self.xmas.iter().any(|(l, r)| l >= r)
+    }
+
+    pub fn product(&self) -> u64 {
+        assert!(!self.is_empty());
+        self.xmas
+            .iter()
+            .map(|(left, right)| (*right as u64) - (*left as u64))
+            .product()
+    }
+}
@@ -3,50 +3,25 @@ use args::Args;
 
 use std::{
     collections::{HashMap, VecDeque},
-    env,
+    env, io,
 };
 
 use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
 use clap::Parser;
-use phf::{phf_map, Map};
-use regex::Regex;
-use Op::*;
-
-static MAP: Map<&'static str, usize> = phf_map! {
-    "x" => 0,
-    "m" => 1,
-    "a" => 2,
-    "s" => 3,
-};
+use day_19::{parse_input, Op::*, Part, PartRange, Rule, WorkFlow};
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let session = args.session.or_else(|| env::var("SESSION").ok());
 
-    let input = if let Some(s) = args.input {
-        s
-    } else {
-        get_puzzle_input(2023, 19, session).context("Could not retrieve puzzle input")?
+    let input = match args.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        Option::None => get_puzzle_input(2023, 19, session).context("Could not retrieve puzzle input")?,
     };
 
-    let re = Regex::new(r"^(\w+)\{(.*)\}$").unwrap();
-    let flows: HashMap<String, WorkFlow> = input
-        .split("\n\n")
-        .next()
-        .unwrap()
-        .lines()
-        .map(|l| WorkFlow::from_str(l, &re))
-        .collect();
-
-    let parts: Vec<_> = input
-        .split("\n\n")
-        .skip(1)
-        .next()
-        .unwrap()
-        .lines()
-        .map(Part::from_str)
-        .collect();
+    let (flows, parts) = parse_input(&input)?;
 
     part_one_vm(&flows, &parts);
     part_two_vm(&flows);
@@ -116,7 +91,7 @@ fn part_two_vm(flows: &HashMap<String, WorkFlow>) {
     println!("{answer}");
 }
 
-fn f2<'a>(flow: &'a WorkFlow, mut range: PartRange) -> impl Iterator<Item = (&'a str, PartRange)> {
+fn f2(flow: &WorkFlow, mut range: PartRange) -> impl Iterator<Item = (&str, PartRange)> {
     // the ranges already processed
     let mut next = vec![];
 
@@ -152,120 +127,3 @@ fn f2<'a>(flow: &'a WorkFlow, mut range: PartRange) -> impl Iterator<Item = (&'a
 fn split_range((left, right): (i32, i32), value: i32) -> ((i32, i32), (i32, i32)) {
     ((left, value), (value, right))
 }
-
-#[derive(Debug, Clone)]
-struct WorkFlow {
-    rules: Vec<Rule>,
-}
-
-impl WorkFlow {
-    fn from_str(v: &str, re: &Regex) -> (String, Self) {
-        let (_m, [id, rules]) = re.captures(v).map(|c| c.extract()).unwrap();
-        let rules: Vec<_> = rules.split(',').map(Rule::from_str).collect();
-        (id.into(), Self { rules })
-    }
-}
-
-/// Represents a single rule in a workflow
-#[derive(Debug, Clone)]
-struct Rule {
-    prop: String,
-    op: Op,
-    value: i32,
-    destination: String,
-}
-
-impl Rule {
-    fn from_str(v: &str) -> Self {
-        // no op
-        if !v.contains(':') {
-            return Self {
-                prop: ".".into(),
-                op: None,
-                value: 0,
-                destination: v.into(),
-            };
-        }
-        let v: Vec<_> = v.split(':').collect();
-
-        let op = match &v[0][1..2] {
-            ">" => Gt,
-            "<" => Lt,
-            c => panic!("expected < or >, got {c}"),
-        };
-        Self {
-            prop: v[0][0..1].into(),
-            op,
-            value: i32::from_str_radix(&v[0][2..], 10).unwrap(),
-            destination: v[1].into(),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Op {
-    Lt,
-    Gt,
-    None,
-}
-
-#[derive(Debug, Clone, Copy)]
-struct Part {
-    xmas: [i32; 4],
-}
-
-impl Part {
-    fn from_str(v: &str) -> Self {
-        let mut xmas = [0; 4];
-        // remove '{'  and '}' delimiters
-        let v = &v[1..v.len() - 1];
-        for (i, v) in v.split(',').enumerate() {
-            let value = i32::from_str_radix(v.split('=').skip(1).next().unwrap(), 10).unwrap();
-            xmas[i] = value;
-        }
-        Self { xmas }
-    }
-
-    fn get(&self, rule: &Rule) -> i32 {
-        self.xmas[*MAP.get(&rule.prop).unwrap()]
-    }
-
-    fn rating(&self) -> i32 {
-        self.xmas.iter().sum()
-    }
-}
-
-#[derive(Debug, Clone)]
-struct PartRange {
-    xmas: [(i32, i32); 4],
-}
-
-impl PartRange {
-    fn new() -> Self {
-        Self {
-            xmas: [(1, 4001); 4],
-        }
-    }
-
-    fn get(&self, rule: &Rule) -> (i32, i32) {
-        self.xmas[*MAP.get(&rule.prop).unwrap()]
-    }
-
-    fn get_mut(&mut self, rule: &Rule) -> &mut (i32, i32) {
-        &mut self.xmas[*MAP.get(&rule.prop).unwrap()]
-    }
-
-    fn is_empty(&self) -> bool {
-        // left inclusive, right exclusive
-        // so 1..1 = empty
-        self.xmas.iter().any(|(l, r)| l >= r)
-    }
-
-    fn product(&self) -> u64 {
-        assert!(!self.is_empty());
-        self.xmas
-            .iter()
-            .map(|(left, right)| (*right as u64) - (*left as u64))
-            .product()
-    }
-}
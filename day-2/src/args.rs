@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use clap::{Args as ClapArgs, Parser, Subcommand};
+
+/// Cube Conundrum
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+pub struct Args {
+    #[command(flatten)]
+    pub puzzle: Puzzle,
+
+    #[command(flatten)]
+    pub bag: Bag,
+
+    #[command(subcommand)]
+    pub query: Option<Query>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct Puzzle {
+    /// The puzzle input, or - to read it from stdin
+    #[arg()]
+    pub input: Option<String>,
+
+    /// Advent of code session token
+    #[arg(short, long)]
+    pub session: Option<String>,
+}
+
+/// The cubes in the bag, like "12 red, 13 green, 14 blue"
+#[derive(ClapArgs, Debug)]
+pub struct Bag {
+    /// The cubes in the bag
+    #[arg(short, long, default_value = "12 red, 13 green, 14 blue")]
+    pub bag: String,
+
+    /// A file with the cubes in the bag, separated by commas or newlines, instead of --bag
+    #[arg(long)]
+    pub bag_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Query {
+    /// The fewest cubes of every color the bag could have held, for every game
+    Minimal {
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+
+    /// The games that could have been played with the bag
+    Feasible {
+        #[command(flatten)]
+        puzzle: Puzzle,
+
+        #[command(flatten)]
+        bag: Bag,
+    },
+
+    /// The bag with the fewest cubes that could have played atleast k games
+    Smallest {
+        /// The number of games the bag should be able to play
+        #[arg(short)]
+        k: usize,
+
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+
+    /// Statistics of the cubes drawn of every color, and the most likely bag
+    Stats {
+        /// The most cubes the bag could hold, when estimating its contents
+        #[arg(long, default_value_t = 1000)]
+        max_cubes: u64,
+
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+}
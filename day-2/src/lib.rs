@@ -0,0 +1,332 @@
+pub mod stats;
+
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{bail, Context, Error, Result};
+use regex::Regex;
+
+/// A number of cubes for every color, in the order the colors were first named.
+/// Colors that are not named have no cubes.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cubes(Vec<(String, u32)>);
+
+impl Cubes {
+    pub fn get(&self, color: &str) -> u32 {
+        self.0
+            .iter()
+            .find(|(c, _)| c == color)
+            .map_or(0, |(_, count)| *count)
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(color, _)| color.as_str())
+    }
+
+    /// Add `count` cubes of `color`
+    pub fn add(&mut self, color: &str, count: u32) -> Result<()> {
+        match self.0.iter_mut().find(|(c, _)| c == color) {
+            Some((_, total)) => {
+                *total = total
+                    .checked_add(count)
+                    .context("Too many cubes in a single set")?
+            }
+            None => self.0.push((color.to_string(), count)),
+        }
+        Ok(())
+    }
+
+    /// Raise the number of cubes of every color to atleast the number in `other`
+    pub fn raise_to(&mut self, other: &Cubes) {
+        for (color, count) in other.0.iter() {
+            match self.0.iter_mut().find(|(c, _)| c == color) {
+                Some((_, total)) => *total = (*total).max(*count),
+                None => self.0.push((color.clone(), *count)),
+            }
+        }
+    }
+
+    /// Whether there are atleast as many cubes of every color as in `other`
+    pub fn contains(&self, other: &Cubes) -> bool {
+        other
+            .0
+            .iter()
+            .all(|(color, count)| *count <= self.get(color))
+    }
+
+    /// The number of cubes of all colors together
+    pub fn total(&self) -> u64 {
+        self.0.iter().map(|(_, count)| *count as u64).sum()
+    }
+
+    /// The numbers of cubes of `colors` multiplied together
+    pub fn power<'a>(&self, colors: impl IntoIterator<Item = &'a str>) -> u64 {
+        colors
+            .into_iter()
+            .map(|color| self.get(color) as u64)
+            .product()
+    }
+}
+
+/// Parses a set of cubes like `3 blue, 4 red`, newlines separate cubes like commas
+impl FromStr for Cubes {
+    type Err = Error;
+
+    fn from_str(v: &str) -> Result<Self> {
+        let mut cubes = Cubes::default();
+        for pair in v.split([',', '\n']).filter(|pair| !pair.trim().is_empty()) {
+            let mut words = pair.split_ascii_whitespace();
+
+            let number_of_cubes: u32 = words
+                .next()
+                .context("Expected a number of cubes")?
+                .parse()
+                .context("Should be a number")?;
+            let color = words.next().context("Expected a color")?;
+            if words.next().is_some() {
+                bail!("Expected a single color, got: {}", pair.trim());
+            }
+            cubes.add(color, number_of_cubes)?;
+        }
+        Ok(cubes)
+    }
+}
+
+impl Display for Cubes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cubes = self
+            .0
+            .iter()
+            .map(|(color, count)| format!("{} {}", count, color))
+            .collect::<Vec<_>>();
+        write!(f, "{}", cubes.join(", "))
+    }
+}
+
+/// A single game, with every set of cubes that was revealed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Cubes>,
+}
+
+impl Game {
+    /// The fewest cubes of every color the bag could have held to play this game
+    pub fn minimal_bag(&self) -> Cubes {
+        let mut bag = Cubes::default();
+        for set in self.sets.iter() {
+            bag.raise_to(set);
+        }
+        bag
+    }
+
+    /// Whether this game could have been played with `bag`
+    pub fn is_feasible(&self, bag: &Cubes) -> bool {
+        self.sets.iter().all(|set| bag.contains(set))
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Game>> {
+    let re = Regex::new(r"^Game (\d+): (.*)").unwrap();
+
+    let mut games = vec![];
+    for line in input.lines() {
+        // extract id
+        let (_full, [id, sets]) = re
+            .captures(line)
+            .with_context(|| format!("Invalid puzzle input: expected a game, got: {}", line))?
+            .extract();
+        let id = id.parse()?;
+
+        let sets: Result<Vec<_>> = sets.split(';').map(str::parse).collect();
+        games.push(Game { id, sets: sets? });
+    }
+    Ok(games)
+}
+
+/// Every color named in any of the games, in the order they were first named
+pub fn colors(games: &[Game]) -> Vec<&str> {
+    let mut colors = vec![];
+    for color in games
+        .iter()
+        .flat_map(|game| game.sets.iter().flat_map(Cubes::colors))
+    {
+        if !colors.contains(&color) {
+            colors.push(color);
+        }
+    }
+    colors
+}
+
+/// The sum of the ids of the games `bag` could have played, and the sum of the powers
+/// of the minimal bag of every game. The power only covers the colors the games show,
+/// a color only the bag names would multiply it by 0.
+pub fn sum_parts(games: &[Game], bag: &Cubes) -> (u32, u64) {
+    let colors = colors(games);
+    let feasible = games.iter().filter(|game| game.is_feasible(bag));
+    let powers = games
+        .iter()
+        .map(|game| game.minimal_bag().power(colors.iter().copied()));
+    (feasible.map(|game| game.id).sum(), powers.sum())
+}
+
+/// The bag with the fewest cubes in total that makes atleast `k` games feasible,
+/// along with the games it makes feasible. None when there are fewer than `k` games.
+///
+/// For every color but the last, every number of cubes some game needs is tried,
+/// the last color then holds just enough cubes for the k-th game that is still feasible.
+/// This takes O(n^c log n) time for n games of c colors.
+pub fn smallest_bag(games: &[Game], k: usize) -> Option<(Cubes, Vec<&Game>)> {
+    if games.len() < k {
+        return None;
+    }
+    let minimal_bags = games.iter().map(Game::minimal_bag).collect::<Vec<_>>();
+    let colors = colors(games);
+    let Some((last, colors)) = colors.split_last() else {
+        // no game needs any cubes, the empty bag makes them all feasible
+        return Some((Cubes::default(), games.iter().collect()));
+    };
+
+    let candidates = (0..games.len()).collect::<Vec<_>>();
+    let mut best: Option<(u64, Vec<u32>)> = None;
+    search(
+        &minimal_bags,
+        k,
+        colors,
+        last,
+        &candidates,
+        &mut vec![],
+        &mut best,
+    );
+
+    let (_total, counts) = best?;
+    let mut bag = Cubes::default();
+    for (color, count) in colors.iter().chain([last]).zip(counts) {
+        bag.add(color, count).unwrap();
+    }
+    let feasible = games.iter().filter(|game| game.is_feasible(&bag)).collect();
+    Some((bag, feasible))
+}
+
+/// Try every number of cubes of the first color in `colors` for the games that are still feasible
+fn search(
+    minimal_bags: &[Cubes],
+    k: usize,
+    colors: &[&str],
+    last: &str,
+    candidates: &[usize],
+    counts: &mut Vec<u32>,
+    best: &mut Option<(u64, Vec<u32>)>,
+) {
+    let total = counts.iter().map(|count| *count as u64).sum::<u64>();
+    if candidates.len() < k || best.as_ref().is_some_and(|(least, _)| *least <= total) {
+        return;
+    }
+
+    let Some((color, colors)) = colors.split_first() else {
+        // enough cubes of the last color for the k-th game
+        let mut needed = candidates
+            .iter()
+            .map(|i| minimal_bags[*i].get(last))
+            .collect::<Vec<_>>();
+        needed.sort_unstable();
+        let count = if k == 0 { 0 } else { needed[k - 1] };
+        if best
+            .as_ref()
+            .is_none_or(|(least, _)| total + (count as u64) < *least)
+        {
+            let mut counts = counts.clone();
+            counts.push(count);
+            *best = Some((total + count as u64, counts));
+        }
+        return;
+    };
+
+    let mut options = [0]
+        .into_iter()
+        .chain(candidates.iter().map(|i| minimal_bags[*i].get(color)))
+        .collect::<Vec<_>>();
+    options.sort_unstable();
+    options.dedup();
+    for count in options {
+        let candidates = candidates
+            .iter()
+            .copied()
+            .filter(|i| minimal_bags[*i].get(color) <= count)
+            .collect::<Vec<_>>();
+        counts.push(count);
+        search(minimal_bags, k, colors, last, &candidates, counts, best);
+        counts.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn example() {
+        let games = parse_input(EXAMPLE).unwrap();
+        let bag = "12 red, 13 green, 14 blue".parse::<Cubes>().unwrap();
+        let feasible = games.iter().filter(|game| game.is_feasible(&bag));
+        assert_eq!(feasible.map(|game| game.id).sum::<u32>(), 8);
+
+        let colors = colors(&games);
+        assert_eq!(colors, ["blue", "red", "green"]);
+        let powers = games
+            .iter()
+            .map(|game| game.minimal_bag().power(colors.clone()));
+        assert_eq!(powers.sum::<u64>(), 2286);
+    }
+
+    #[test]
+    fn bag_with_an_extra_color() {
+        let games = parse_input(EXAMPLE).unwrap();
+        let bag = "12 red, 13 green, 14 blue, 3 yellow"
+            .parse::<Cubes>()
+            .unwrap();
+        assert_eq!(sum_parts(&games, &bag), (8, 2286));
+    }
+
+    #[test]
+    fn arbitrary_colors() {
+        let games = parse_input("Game 7: 2 teal, 1 red; 3 mauve, 1 teal\nGame 8: 4 teal").unwrap();
+        assert_eq!(games[0].minimal_bag().to_string(), "2 teal, 1 red, 3 mauve");
+        assert!(games[1].is_feasible(&"4 teal\n1 red".parse().unwrap()));
+        assert!(!games[0].is_feasible(&"4 teal\n1 red".parse().unwrap()));
+        assert!("4 teal cubes".parse::<Cubes>().is_err());
+    }
+
+    /// The smallest bag holds no more cubes than the minimal bag of any k games together
+    #[test]
+    fn smallest_bags() {
+        let games = parse_input(EXAMPLE).unwrap();
+        let minimal_bags = games.iter().map(Game::minimal_bag).collect::<Vec<_>>();
+        for k in 0..=games.len() {
+            let (bag, feasible) = smallest_bag(&games, k).unwrap();
+            assert!(feasible.len() >= k);
+
+            // compare with every subset of k games
+            let least = (0u32..1 << games.len())
+                .filter(|subset| subset.count_ones() as usize == k)
+                .map(|subset| {
+                    let mut bag = Cubes::default();
+                    for (i, minimal_bag) in minimal_bags.iter().enumerate() {
+                        if subset & (1 << i) != 0 {
+                            bag.raise_to(minimal_bag);
+                        }
+                    }
+                    bag.total()
+                })
+                .min()
+                .unwrap();
+            assert_eq!(bag.total(), least, "k = {}", k);
+        }
+        assert!(smallest_bag(&games, 6).is_none());
+    }
+}
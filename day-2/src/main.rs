@@ -1,90 +1,159 @@
-use anyhow::{anyhow, Context, Result};
+mod args;
+
+use std::{env, fs, io};
+
+use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
+use args::{Args, Bag, Puzzle, Query};
 use clap::Parser;
-use regex::Regex;
+use day_2::{
+    colors, parse_input, smallest_bag,
+    stats::{color_stats, estimate_bag},
+    sum_parts, Cubes, Game,
+};
 
-/// Cube Conundrum
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// The puzzle input
-    #[arg()]
-    input: Option<String>,
-
-    /// Advent of code session token
-    #[arg(short, long)]
-    session: Option<String>,
-}
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    let re = Regex::new(r"^Game (\d+): (.*)").unwrap();
+    match args.query {
+        None => parts(&read_input(args.puzzle)?, &read_bag(args.bag)?),
+        Some(Query::Minimal { puzzle }) => minimal(&read_input(puzzle)?),
+        Some(Query::Feasible { puzzle, bag }) => feasible(&read_input(puzzle)?, &read_bag(bag)?),
+        Some(Query::Smallest { k, puzzle }) => smallest(&read_input(puzzle)?, k),
+        Some(Query::Stats { max_cubes, puzzle }) => stats(&read_input(puzzle)?, max_cubes),
+    }
+}
 
-    let input = if let Some(s) = args.input {
-        s
-    } else {
-        get_puzzle_input(2023, 2, args.session).context("Could not retrieve puzzle input")?
+fn read_input(puzzle: Puzzle) -> Result<Vec<Game>> {
+    let session = puzzle.session.or_else(|| env::var("SESSION").ok());
+    let input = match puzzle.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        None => get_puzzle_input(2023, 2, session).context("Could not retrieve puzzle input")?,
     };
+    parse_input(&input)
+}
 
-    let mut sum_part_one = 0;
-    let mut sum_part_two = 0;
+fn read_bag(bag: Bag) -> Result<Cubes> {
+    match bag.bag_file {
+        Some(path) => fs::read_to_string(&path)
+            .with_context(|| format!("Could not read {}", path.display()))?
+            .parse(),
+        None => bag.bag.parse(),
+    }
+    .context("Expected cubes like: 12 red, 13 green, 14 blue")
+}
 
-    for line in input.lines() {
-        // extract id
-        for (_full, [id, sets]) in re.captures_iter(line).map(|c| c.extract()) {
-            let id = u32::from_str_radix(id, 10)?;
+fn parts(games: &[Game], bag: &Cubes) -> Result<()> {
+    let (sum_part_one, sum_part_two) = sum_parts(games, bag);
+    println!("part one: {}", sum_part_one);
+    println!("part two: {}", sum_part_two);
 
-            let sets: Result<Vec<_>> = sets.split(';').map(parse_set_of_cubes).collect();
-            let sets = sets?;
+    Ok(())
+}
 
-            // part one
-            if sets.iter().all(|(r,g,b)| *r <= 12 && *g <= 13 && *b <= 14) {
-                sum_part_one += id;
-            }
+fn minimal(games: &[Game]) -> Result<()> {
+    let colors = colors(games);
+    for game in games {
+        let bag = game.minimal_bag();
+        let power = bag.power(colors.iter().copied());
+        println!("Game {}: {} (power {})", game.id, bag, power);
+    }
+    Ok(())
+}
 
-            // part two
-            let (mut red_max, mut green_max, mut blue_max) = (0, 0, 0);
-            for (r,g,b) in sets {
-                red_max = u32::max(red_max, r);
-                green_max = u32::max(green_max, g);
-                blue_max = u32::max(blue_max, b);
-            }
-            sum_part_two += red_max*green_max*blue_max;
-        }
+fn feasible(games: &[Game], bag: &Cubes) -> Result<()> {
+    let feasible = games
+        .iter()
+        .filter(|game| game.is_feasible(bag))
+        .collect::<Vec<_>>();
+    for game in feasible.iter() {
+        println!("Game {}", game.id);
     }
-    println!("part one: {}", sum_part_one);
-    println!("part two: {}", sum_part_two);
+    println!(
+        "{} of {} games could have been played with {}, the sum of their ids is {}",
+        feasible.len(),
+        games.len(),
+        bag,
+        feasible.iter().map(|game| game.id).sum::<u32>()
+    );
+    Ok(())
+}
 
+fn smallest(games: &[Game], k: usize) -> Result<()> {
+    let (bag, feasible) = smallest_bag(games, k)
+        .with_context(|| format!("Expected atleast {} games, got {}", k, games.len()))?;
+    let ids = feasible
+        .iter()
+        .map(|game| game.id.to_string())
+        .collect::<Vec<_>>();
+    println!(
+        "{} ({} cubes) could have played {} games: {}",
+        bag,
+        bag.total(),
+        feasible.len(),
+        ids.join(", ")
+    );
     Ok(())
 }
 
-fn parse_set_of_cubes(v: &str) -> Result<(u32, u32, u32)> {
-    let (mut red, mut green, mut blue) = (0, 0, 0);
-    for pair in v.split(',') {
-        let mut words = pair.split_ascii_whitespace();
+fn stats(games: &[Game], max_cubes: u64) -> Result<()> {
+    let stats = color_stats(games);
+    let estimate = estimate_bag(games, max_cubes);
+    let width = stats
+        .iter()
+        .map(|stats| stats.color.len())
+        .chain([5])
+        .max()
+        .unwrap();
+
+    println!(
+        "{:<width$} {:>5} {:>7} {:>6} {:>9}",
+        "color", "max", "mean", "sets", "estimate"
+    );
+    for stats in stats.iter() {
+        let estimate = estimate
+            .as_ref()
+            .map_or("-".to_string(), |e| e.bag.get(&stats.color).to_string());
+        println!(
+            "{:<width$} {:>5} {:>7.2} {:>6} {:>9}",
+            stats.color, stats.max, stats.mean, stats.sets, estimate
+        );
+    }
 
-        let number_of_cubes: u32 =
-            u32::from_str_radix(words.next().unwrap(), 10).context("Should be a number")?;
-        let color = words.next().unwrap();
+    // the number of sets that drew every number of cubes, per color
+    println!();
+    print!("{:>5}", "drawn");
+    for stats in stats.iter() {
+        print!(" {:>width$}", stats.color);
+    }
+    println!();
+    let most = stats.iter().map(|stats| stats.max).max().unwrap_or(0);
+    for count in 0..=most as usize {
+        print!("{:>5}", count);
+        for stats in stats.iter() {
+            print!(" {:>width$}", stats.distribution.get(count).unwrap_or(&0));
+        }
+        println!();
+    }
 
-        match color {
-            "red" => {
-                red += number_of_cubes;
-                Ok(())
+    println!();
+    match estimate {
+        Some(estimate) => {
+            println!(
+                "most likely bag: {} ({} cubes), log likelihood {:.3}",
+                estimate.bag,
+                estimate.bag.total(),
+                estimate.log_likelihood
+            );
+            if estimate.bounded {
+                println!(
+                    "the likelihood still grows at {} cubes, raise --max-cubes for a larger bag",
+                    max_cubes
+                );
             }
-            "green" => {
-                green += number_of_cubes;
-                Ok(())
-            }
-            "blue" => {
-                blue += number_of_cubes;
-                Ok(())
-            }
-            _ => Err(anyhow!(
-                "Invalid puzzle input: for pair of number of cubes and color: {} ",
-                v
-            )),
-        }?;
+        }
+        None => println!("the games need more than {} cubes", max_cubes),
     }
-    Ok((red, green, blue))
+    Ok(())
 }
@@ -0,0 +1,203 @@
+//! What the recorded games tell about the bag they were all played with.
+
+use crate::{colors, Cubes, Game};
+
+/// How many cubes of a single color were drawn in the sets of all games
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorStats {
+    pub color: String,
+    /// The most cubes drawn in a single set
+    pub max: u32,
+    /// The mean number of cubes drawn per set, counting sets without this color as 0
+    pub mean: f64,
+    /// The number of sets this color was drawn in
+    pub sets: usize,
+    /// For every number of cubes, the number of sets that drew that many
+    pub distribution: Vec<usize>,
+}
+
+/// Statistics for every color named in the games, in the order they were first named
+pub fn color_stats(games: &[Game]) -> Vec<ColorStats> {
+    let sets = games.iter().flat_map(|game| game.sets.iter());
+    let total_sets = sets.clone().count();
+
+    colors(games)
+        .into_iter()
+        .map(|color| {
+            let counts = sets.clone().map(|set| set.get(color)).collect::<Vec<_>>();
+            let max = counts.iter().copied().max().unwrap_or(0);
+            let mut distribution = vec![0; max as usize + 1];
+            for count in counts.iter() {
+                distribution[*count as usize] += 1;
+            }
+            ColorStats {
+                color: color.to_string(),
+                max,
+                mean: counts.iter().map(|count| *count as f64).sum::<f64>() / total_sets as f64,
+                sets: total_sets - distribution[0],
+                distribution,
+            }
+        })
+        .collect()
+}
+
+/// The bag that makes the recorded games most likely
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub bag: Cubes,
+    /// The natural logarithm of the likelihood of all sets, given the bag
+    pub log_likelihood: f64,
+    /// Whether the likelihood was still growing when the bag reached the maximum number of cubes,
+    /// a larger bag may be more likely
+    pub bounded: bool,
+}
+
+/// The maximum likelihood estimate of the cubes in the bag, with atmost `max_cubes` cubes in total.
+/// None when the games need more than `max_cubes` cubes. Games that draw no cubes tell nothing,
+/// the estimate is then an empty bag.
+///
+/// Every set is taken to be drawn without replacement from the full bag, so a set follows a
+/// multivariate hypergeometric distribution: with `b` cubes of every color and `t` cubes in total,
+/// a set of `s` cubes of every color and `n` cubes in total has a likelihood of
+/// `prod(C(b, s)) / C(t, n)`.
+///
+/// For a fixed total, the log likelihood is a sum of concave functions, one per color,
+/// so adding cubes one at a time to the color that gains the most finds the best bag of every total.
+pub fn estimate_bag(games: &[Game], max_cubes: u64) -> Option<Estimate> {
+    let stats = color_stats(games);
+    // for every number of cubes, the number of sets that drew that many cubes in total
+    let mut set_sizes = vec![];
+    for set in games.iter().flat_map(|game| game.sets.iter()) {
+        let size = set.total() as usize;
+        if set_sizes.len() <= size {
+            set_sizes.resize(size + 1, 0);
+        }
+        set_sizes[size] += 1;
+    }
+
+    // the bag needs atleast the most cubes ever drawn of every color
+    let mut bag = stats
+        .iter()
+        .map(|stats| stats.max as u64)
+        .collect::<Vec<_>>();
+    let mut total = bag.iter().sum::<u64>();
+    if total > max_cubes {
+        return None;
+    }
+    let mut log_likelihood = stats
+        .iter()
+        .map(|stats| log_likelihood_of(&stats.distribution, stats.max as u64))
+        .sum::<f64>()
+        - log_likelihood_of(&set_sizes, total);
+
+    let mut best = (log_likelihood, bag.clone());
+    while total < max_cubes {
+        // add the cube that gains the most, there is nothing to add without colors
+        let Some((color, color_gain)) = stats
+            .iter()
+            .enumerate()
+            .map(|(i, stats)| (i, gain(&stats.distribution, bag[i])))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        else {
+            break;
+        };
+        bag[color] += 1;
+        log_likelihood += color_gain - gain(&set_sizes, total);
+        total += 1;
+        if log_likelihood > best.0 {
+            best = (log_likelihood, bag.clone());
+        }
+    }
+
+    let (log_likelihood, counts) = best;
+    let bounded = counts.iter().sum::<u64>() == max_cubes;
+    let mut bag = Cubes::default();
+    for (stats, count) in stats.iter().zip(counts) {
+        bag.add(&stats.color, count.try_into().ok()?).ok()?;
+    }
+    Some(Estimate {
+        bag,
+        log_likelihood,
+        bounded,
+    })
+}
+
+/// The sum of `ln C(b, k)` over every set, where `distribution[k]` sets drew `k` cubes
+fn log_likelihood_of(distribution: &[usize], b: u64) -> f64 {
+    distribution
+        .iter()
+        .enumerate()
+        .map(|(k, sets)| *sets as f64 * ln_choose(b, k as u64))
+        .sum()
+}
+
+/// How much [`log_likelihood_of`] grows from `b` to `b + 1` cubes,
+/// as `ln C(b + 1, k) - ln C(b, k) = ln((b + 1) / (b + 1 - k))`
+fn gain(distribution: &[usize], b: u64) -> f64 {
+    distribution
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(k, sets)| *sets as f64 * ((b + 1) as f64 / (b + 1 - k as u64) as f64).ln())
+        .sum()
+}
+
+/// The natural logarithm of the binomial coefficient `C(n, k)`, for `k <= n`
+fn ln_choose(n: u64, k: u64) -> f64 {
+    (1..=k).map(|i| ((n - k + i) as f64 / i as f64).ln()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn distributions() {
+        let games = parse_input("Game 1: 3 red, 1 blue; 1 red\nGame 2: 2 blue").unwrap();
+        let stats = color_stats(&games);
+        assert_eq!(stats[0].color, "red");
+        assert_eq!(stats[0].max, 3);
+        assert_eq!(stats[0].sets, 2);
+        assert_eq!(stats[0].distribution, [1, 1, 0, 1]);
+        assert!((stats[0].mean - 4.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats[1].distribution, [1, 1, 1]);
+    }
+
+    /// The estimate is the best bag of all bags up to the maximum number of cubes
+    #[test]
+    fn estimate_is_most_likely() {
+        let games =
+            parse_input("Game 1: 3 red, 1 blue; 2 red, 2 blue\nGame 2: 4 red; 1 blue, 2 red")
+                .unwrap();
+        let estimate = estimate_bag(&games, 30).unwrap();
+
+        let likelihood = |red: u64, blue: u64| {
+            let sets = games.iter().flat_map(|game| game.sets.iter());
+            sets.map(|set| {
+                let (r, b) = (set.get("red") as u64, set.get("blue") as u64);
+                ln_choose(red, r) + ln_choose(blue, b) - ln_choose(red + blue, r + b)
+            })
+            .sum::<f64>()
+        };
+        let (red, blue) = (4..=30)
+            .flat_map(|red| (2..=30 - red).map(move |blue| (red, blue)))
+            .max_by(|a, b| likelihood(a.0, a.1).total_cmp(&likelihood(b.0, b.1)))
+            .unwrap();
+        assert_eq!(estimate.bag.get("red") as u64, red);
+        assert_eq!(estimate.bag.get("blue") as u64, blue);
+        assert!((estimate.log_likelihood - likelihood(red, blue)).abs() < 1e-9);
+
+        assert!(estimate_bag(&games, 5).is_none());
+    }
+
+    #[test]
+    fn estimate_without_cubes() {
+        for input in ["", "Game 1: "] {
+            let games = parse_input(input).unwrap();
+            let estimate = estimate_bag(&games, 30).unwrap();
+            assert_eq!(estimate.bag, Cubes::default());
+            assert!(!estimate.bounded);
+        }
+    }
+}
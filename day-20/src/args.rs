@@ -1,4 +1,4 @@
-use std::env;
+use std::{env, io};
 
 use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
@@ -8,7 +8,7 @@ use clap::Parser;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = Option::None)]
 pub struct Args {
-    /// The puzzle input
+    /// The puzzle input, or - to read it from stdin
     #[arg()]
     pub input: Option<String>,
 
@@ -21,9 +21,9 @@ pub fn get_input(year: u32, day: u32) -> Result<String> {
     let args = Args::parse();
     let session = args.session.or_else(|| env::var("SESSION").ok());
 
-    if let Some(s) = args.input {
-        Ok(s)
-    } else {
-        Ok(get_puzzle_input(year, day, session).context("Could not retrieve puzzle input")?)
+    match args.input {
+        Some(s) if s == "-" => Ok(io::read_to_string(io::stdin())?),
+        Some(s) => Ok(s),
+        None => Ok(get_puzzle_input(year, day, session).context("Could not retrieve puzzle input")?),
     }
 }
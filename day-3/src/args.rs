@@ -0,0 +1,51 @@
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use day_3::{Aggregate, Arity};
+
+/// Gear Ratios
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+pub struct Args {
+    #[command(flatten)]
+    pub puzzle: Puzzle,
+
+    #[command(subcommand)]
+    pub query: Option<Query>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct Puzzle {
+    /// The puzzle input, or - to read it from stdin
+    #[arg()]
+    pub input: Option<String>,
+
+    /// Advent of code session token
+    #[arg(short, long)]
+    pub session: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Query {
+    /// The symbols next to a number of numbers, with those numbers and their aggregate
+    Symbols {
+        /// The symbols to look for, like "*#", every symbol when left out
+        #[arg(long, default_value = "")]
+        symbols: String,
+
+        /// The number of numbers next to a symbol: k for exactly k, k+ for atleast k
+        #[arg(short, long, default_value = "1+")]
+        arity: Arity,
+
+        /// How to combine the numbers next to a symbol: sum, product or max
+        #[arg(long, default_value = "product")]
+        aggregate: Aggregate,
+
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+
+    /// The numbers next to no symbol
+    Lone {
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+}
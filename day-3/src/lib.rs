@@ -0,0 +1,348 @@
+use std::{fmt::Display, str::FromStr};
+
+use anyhow::{bail, Context, Error, Result};
+
+/// A number in the schematic, on a single row. Columns count characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    /// The column of the first digit
+    pub start: usize,
+    /// The column just past the last digit
+    pub end: usize,
+}
+
+/// Any character in the schematic that is not an ascii digit or a period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub ch: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// The engine schematic, with an index of which numbers and symbols are next to each other
+#[derive(Debug, Clone)]
+pub struct Schematic {
+    pub n_columns: usize,
+    pub n_rows: usize,
+    /// Every number in the schematic, row by row
+    pub numbers: Vec<Number>,
+    /// Every symbol in the schematic, row by row
+    pub symbols: Vec<Symbol>,
+    /// For every number, the indices of the symbols next to it, also diagonally
+    number_symbols: Vec<Vec<usize>>,
+    /// For every symbol, the indices of the numbers next to it, also diagonally
+    symbol_numbers: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    /// The symbols next to a number
+    pub fn adjacent_symbols(&self, number: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_symbols[number]
+            .iter()
+            .map(|i| &self.symbols[*i])
+    }
+
+    /// The numbers next to a symbol
+    pub fn adjacent_numbers(&self, symbol: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_numbers[symbol]
+            .iter()
+            .map(|i| &self.numbers[*i])
+    }
+
+    /// The numbers next to atleast one symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(self.number_symbols.iter())
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// The numbers next to no symbol at all
+    pub fn lone_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(self.number_symbols.iter())
+            .filter(|(_, symbols)| symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// The symbols out of `symbols` that are next to `arity` numbers, with those numbers.
+    /// An empty set of symbols matches any symbol.
+    pub fn query<'a>(
+        &'a self,
+        symbols: &'a [char],
+        arity: Arity,
+    ) -> impl Iterator<Item = (&'a Symbol, Vec<&'a Number>)> + 'a {
+        self.symbols
+            .iter()
+            .zip(self.symbol_numbers.iter())
+            .filter(move |(symbol, numbers)| {
+                (symbols.is_empty() || symbols.contains(&symbol.ch)) && arity.matches(numbers.len())
+            })
+            .map(|(symbol, numbers)| (symbol, numbers.iter().map(|i| &self.numbers[*i]).collect()))
+    }
+}
+
+/// How many numbers a symbol should be next to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arity {
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl Arity {
+    pub fn matches(&self, count: usize) -> bool {
+        match self {
+            Arity::Exactly(k) => count == *k,
+            Arity::AtLeast(k) => count >= *k,
+        }
+    }
+}
+
+/// Parses `k` as exactly k and `k+` as atleast k
+impl FromStr for Arity {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (k, at_least) = match s.strip_suffix('+') {
+            Some(k) => (k, true),
+            None => (s, false),
+        };
+        let k = k
+            .parse()
+            .with_context(|| format!("Expected k or k+, got: {}", s))?;
+        Ok(if at_least {
+            Arity::AtLeast(k)
+        } else {
+            Arity::Exactly(k)
+        })
+    }
+}
+
+/// Combines the numbers next to a symbol into a single value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Product,
+    Max,
+}
+
+impl Aggregate {
+    /// The aggregate of the values of `numbers`, None when it overflows.
+    /// The sum and product of no numbers are 0 and 1, their max is 0.
+    pub fn apply<'a>(&self, numbers: impl IntoIterator<Item = &'a Number>) -> Option<u128> {
+        let mut values = numbers.into_iter().map(|number| number.value as u128);
+        match self {
+            Aggregate::Sum => values.try_fold(0u128, |sum, value| sum.checked_add(value)),
+            Aggregate::Product => {
+                values.try_fold(1u128, |product, value| product.checked_mul(value))
+            }
+            Aggregate::Max => Some(values.max().unwrap_or(0)),
+        }
+    }
+}
+
+impl Display for Aggregate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Product => "product",
+            Aggregate::Max => "max",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "product" => Ok(Aggregate::Product),
+            "max" => Ok(Aggregate::Max),
+            _ => bail!("Expected sum, product or max, got: {}", s),
+        }
+    }
+}
+
+/// Parse the schematic, every row should have the same number of characters.
+/// Any character other than an ascii digit or a period is a symbol, also outside of ascii.
+pub fn parse_input(input: &str) -> Result<Schematic> {
+    let rows = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let n_columns = rows.first().map_or(0, |row| row.len());
+    if n_columns == 0 {
+        bail!("Puzzle input should not start with an empty line");
+    }
+    let n_rows = rows.len();
+
+    // report every row that is off, not just the first
+    let bad_rows = rows
+        .iter()
+        .enumerate()
+        .filter(|(_, row)| row.len() != n_columns)
+        .map(|(y, row)| format!("row {} has {}", y + 1, row.len()))
+        .collect::<Vec<_>>();
+    if !bad_rows.is_empty() {
+        let more = match bad_rows.len() {
+            n if n > 10 => format!(" and {} more", n - 10),
+            _ => String::new(),
+        };
+        bail!(
+            "Every row should have {} characters like the first, but {}{}",
+            n_columns,
+            bad_rows[..bad_rows.len().min(10)].join(", "),
+            more
+        );
+    }
+
+    // the number at every cell, as an index into numbers
+    let mut number_at = rows
+        .iter()
+        .map(|row| vec![None; row.len()])
+        .collect::<Vec<_>>();
+    let mut numbers = vec![];
+    let mut symbols = vec![];
+    for (y, row) in rows.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let ch = row[x];
+            if ch.is_ascii_digit() {
+                let start = x;
+                while x < row.len() && row[x].is_ascii_digit() {
+                    number_at[y][x] = Some(numbers.len());
+                    x += 1;
+                }
+                let digits = row[start..x].iter().collect::<String>();
+                let value = digits
+                    .parse()
+                    .with_context(|| format!("Number {} is too large", digits))?;
+                numbers.push(Number {
+                    value,
+                    row: y,
+                    start,
+                    end: x,
+                });
+                continue;
+            }
+            if ch != '.' {
+                symbols.push(Symbol { ch, row: y, col: x });
+            }
+            x += 1;
+        }
+    }
+
+    // every symbol looks at the 8 cells around it, a number can fill several of them
+    let mut number_symbols = vec![vec![]; numbers.len()];
+    let mut symbol_numbers = vec![];
+    for (i, symbol) in symbols.iter().enumerate() {
+        let mut adjacent: Vec<usize> = vec![];
+        for y in symbol.row.saturating_sub(1)..=symbol.row + 1 {
+            for x in symbol.col.saturating_sub(1)..=symbol.col + 1 {
+                let number = number_at
+                    .get(y)
+                    .and_then(|row| row.get(x))
+                    .copied()
+                    .flatten();
+                if let Some(number) = number.filter(|number| !adjacent.contains(number)) {
+                    adjacent.push(number);
+                    number_symbols[number].push(i);
+                }
+            }
+        }
+        symbol_numbers.push(adjacent);
+    }
+
+    Ok(Schematic {
+        n_columns,
+        n_rows,
+        numbers,
+        symbols,
+        number_symbols,
+        symbol_numbers,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn example() {
+        let schematic = parse_input(EXAMPLE).unwrap();
+        let part_one = schematic.part_numbers().map(|n| n.value).sum::<u32>();
+        assert_eq!(part_one, 4361);
+
+        let adjacent = schematic.adjacent_numbers(0).map(|n| n.value);
+        assert_eq!(adjacent.collect::<Vec<_>>(), [467, 35]);
+        let symbols = schematic.adjacent_symbols(0).map(|s| s.ch);
+        assert_eq!(symbols.collect::<Vec<_>>(), ['*']);
+        // 114 is next to nothing
+        assert_eq!(schematic.adjacent_symbols(1).count(), 0);
+    }
+
+    #[test]
+    fn queries() {
+        let schematic = parse_input(EXAMPLE).unwrap();
+        let gears = schematic.query(&['*'], Arity::Exactly(2));
+        let ratios = gears.map(|(_, numbers)| Aggregate::Product.apply(numbers).unwrap());
+        assert_eq!(ratios.sum::<u128>(), 467835);
+
+        let single = schematic
+            .query(&[], Arity::Exactly(1))
+            .map(|(symbol, _)| symbol.ch);
+        assert_eq!(single.collect::<String>(), "#*+$");
+        let at_least = schematic.query(&['$', '*'], "1+".parse().unwrap());
+        let max = at_least.map(|(_, numbers)| Aggregate::Max.apply(numbers).unwrap());
+        assert_eq!(max.collect::<Vec<_>>(), [467, 617, 664, 755]);
+
+        let lone = schematic.lone_numbers().map(|number| number.value);
+        assert_eq!(lone.collect::<Vec<_>>(), [114, 58]);
+        assert!("two".parse::<Arity>().is_err());
+    }
+
+    #[test]
+    fn ragged_rows() {
+        let error = parse_input("12..\n..\n.*.\n3..#").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Every row should have 4 characters like the first, but row 2 has 2, row 3 has 3"
+        );
+        assert!(parse_input("1.\n\n.2").is_err());
+
+        let many = format!("..{}", "\n.".repeat(12));
+        let error = parse_input(&many).unwrap_err().to_string();
+        assert!(error.ends_with("row 11 has 1 and 2 more"), "{}", error);
+    }
+
+    #[test]
+    fn unicode_symbols() {
+        let schematic = parse_input("é12.\n..€.\n7...\r\n.¹..").unwrap();
+        let symbols = schematic.symbols.iter().map(|s| (s.ch, s.col, s.row));
+        assert_eq!(
+            symbols.collect::<Vec<_>>(),
+            [('é', 0, 0), ('€', 2, 1), ('¹', 1, 3)]
+        );
+        let euro = schematic.adjacent_numbers(1).map(|n| n.value);
+        assert_eq!(euro.collect::<Vec<_>>(), [12]);
+        let superscript = schematic.adjacent_numbers(2).map(|n| n.value);
+        assert_eq!(superscript.collect::<Vec<_>>(), [7]);
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use anyhow::{bail, Context, Error, Result};
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub number: usize,
+    pub winning_numbers: Vec<u32>,
+    pub numbers: Vec<u32>,
+}
+
+impl Card {
+    /// The numbers you have that are winning numbers
+    pub fn matching_numbers(&self) -> Vec<u32> {
+        let winning_set: HashSet<_> = self.winning_numbers.iter().collect();
+        self.numbers
+            .iter()
+            .filter(|n| winning_set.contains(n))
+            .copied()
+            .collect()
+    }
+
+    /// One point for the first match, doubled for every match after it.
+    /// An error when the points do not fit in a u64, past 64 matches.
+    pub fn points(&self) -> Result<u64> {
+        match self.matching_numbers().len() {
+            0 => Ok(0),
+            matches => u32::try_from(matches - 1)
+                .ok()
+                .and_then(|doublings| 1u64.checked_shl(doublings))
+                .with_context(|| {
+                    format!(
+                        "Card {} has too many matches to count its points",
+                        self.number
+                    )
+                }),
+        }
+    }
+}
+
+/// What to do when a card wins copies of cards that are not in the table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overflow {
+    /// Fail, the table should have every card that can be won
+    Error,
+    /// Win copies of the cards that are in the table
+    Clamp,
+    /// A card that wins a card that is not in the table wins no copies at all
+    Ignore,
+}
+
+impl FromStr for Overflow {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "error" => Ok(Overflow::Error),
+            "clamp" => Ok(Overflow::Clamp),
+            "ignore" => Ok(Overflow::Ignore),
+            _ => bail!("Expected error, clamp or ignore, got: {}", s),
+        }
+    }
+}
+
+/// The number of instances of every card, originals and copies, in the order of `cards`.
+///
+/// Card `n` with `m` matches wins a copy of cards `n + 1` to `n + m` for every instance of it,
+/// by the numbers of their `Card n:` headers. Cards only win cards with higher numbers,
+/// so going through the cards by number, every card knows how many instances it has
+/// before it hands out its copies, in O(cards × matches).
+pub fn cascade(cards: &[Card], overflow: Overflow) -> Result<Vec<u64>> {
+    cascade_with(cards, overflow, |_, _, _| ())
+}
+
+/// Like [`cascade`], calling `on_copy` with the index of a card in `cards`,
+/// the index of a card it wins and the number of copies it wins.
+pub fn cascade_with(
+    cards: &[Card],
+    overflow: Overflow,
+    mut on_copy: impl FnMut(usize, usize, u64),
+) -> Result<Vec<u64>> {
+    let mut index = HashMap::new();
+    for (i, card) in cards.iter().enumerate() {
+        if index.insert(card.number, i).is_some() {
+            bail!("Card {} is in the table more than once", card.number);
+        }
+    }
+    let mut order = (0..cards.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| cards[*i].number);
+
+    let mut instances = vec![1u64; cards.len()];
+    for i in order {
+        let card = &cards[i];
+        // None for a card past usize::MAX, which can not be in the table either
+        let won = (1..=card.matching_numbers().len())
+            .map(|offset| {
+                let number = card.number.checked_add(offset);
+                (number, number.and_then(|number| index.get(&number)))
+            })
+            .collect::<Vec<_>>();
+        if let Some((missing, _)) = won.iter().find(|(_, i)| i.is_none()) {
+            match (overflow, missing) {
+                (Overflow::Error, Some(missing)) => bail!(
+                    "Card {} wins a copy of card {}, which is not in the table",
+                    card.number,
+                    missing
+                ),
+                (Overflow::Error, None) => bail!(
+                    "Card {} wins a copy of a card past {}, which is not in the table",
+                    card.number,
+                    usize::MAX
+                ),
+                (Overflow::Clamp, _) => {}
+                (Overflow::Ignore, _) => continue,
+            }
+        }
+        for j in won.into_iter().filter_map(|(_, j)| j) {
+            on_copy(i, *j, instances[i]);
+            instances[*j] = instances[*j]
+                .checked_add(instances[i])
+                .context("Too many scratchcards to count")?;
+        }
+    }
+    Ok(instances)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Card>> {
+    let re = Regex::new(r"Card\s+(\d+): (.*)\s+\|\s+(.*)").unwrap();
+
+    let mut cards = vec![];
+    for line in input.lines() {
+        let (_m, [card_number, n1, n2]) = re.captures(line).context("should match")?.extract();
+        cards.push(Card {
+            number: card_number.parse()?,
+            winning_numbers: parse_numbers(n1)?,
+            numbers: parse_numbers(n2)?,
+        });
+    }
+    Ok(cards)
+}
+
+fn parse_numbers(v: &str) -> Result<Vec<u32>> {
+    v.split_ascii_whitespace()
+        .map(|number| Ok(number.parse()?))
+        .collect()
+}
+
+/// Where the instances of a card came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    /// The number of the card
+    pub number: usize,
+    /// The numbers you have that are winning numbers
+    pub matching_numbers: Vec<u32>,
+    pub points: u64,
+    /// The original and all its copies
+    pub instances: u64,
+    /// The numbers of the cards that won copies of this card, with the number of copies they won
+    pub sources: Vec<(usize, u64)>,
+}
+
+/// The provenance of every card, in the order of `cards`
+pub fn provenance(cards: &[Card], overflow: Overflow) -> Result<Vec<Provenance>> {
+    let mut sources = vec![vec![]; cards.len()];
+    let instances = cascade_with(cards, overflow, |from, to, copies| {
+        sources[to].push((cards[from].number, copies));
+    })?;
+    cards
+        .iter()
+        .zip(instances)
+        .zip(sources)
+        .map(|((card, instances), sources)| {
+            Ok(Provenance {
+                number: card.number,
+                matching_numbers: card.matching_numbers(),
+                points: card.points()?,
+                instances,
+                sources,
+            })
+        })
+        .collect()
+}
+
+/// The cards as a graph in the DOT language, with an edge from every card
+/// to every card it won copies of, labeled with the number of copies
+pub fn to_dot(provenance: &[Provenance]) -> String {
+    let mut dot = String::from("digraph scratchcards {\n    rankdir=LR;\n");
+    for card in provenance {
+        dot.push_str(&format!(
+            "    {} [label=\"Card {}\\n{} points, {} instances\"];\n",
+            card.number, card.number, card.points, card.instances
+        ));
+    }
+    for card in provenance {
+        for (source, copies) in card.sources.iter() {
+            dot.push_str(&format!(
+                "    {} -> {} [label=\"{}\"];\n",
+                source, card.number, copies
+            ));
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+    #[test]
+    fn example() {
+        let cards = parse_input(EXAMPLE).unwrap();
+        let points = cards.iter().map(|card| card.points().unwrap());
+        assert_eq!(points.sum::<u64>(), 13);
+        let instances = cascade(&cards, Overflow::Error).unwrap();
+        assert_eq!(instances, [1, 2, 4, 8, 14, 1]);
+    }
+
+    #[test]
+    fn provenance_of_example() {
+        let cards = parse_input(EXAMPLE).unwrap();
+        let provenance = provenance(&cards, Overflow::Error).unwrap();
+        assert_eq!(provenance[3].matching_numbers, [84]);
+        assert_eq!(provenance[3].points, 1);
+        assert_eq!(provenance[3].instances, 8);
+        assert_eq!(provenance[3].sources, [(1, 1), (2, 2), (3, 4)]);
+
+        // copies from all sources add up to the instances besides the original
+        for card in provenance.iter() {
+            let copies = card.sources.iter().map(|(_, copies)| copies).sum::<u64>();
+            assert_eq!(copies + 1, card.instances);
+        }
+
+        let dot = to_dot(&provenance);
+        assert!(dot.starts_with("digraph scratchcards {"));
+        assert!(dot.contains("    4 [label=\"Card 4\\n1 points, 8 instances\"];"));
+        assert!(dot.contains("    3 -> 4 [label=\"4\"];"));
+        assert_eq!(dot.matches("->").count(), 9);
+    }
+
+    /// Copies go by the numbers in the headers, not by the order of the lines
+    #[test]
+    fn shuffled_cards() {
+        let mut cards = parse_input(EXAMPLE).unwrap();
+        cards.reverse();
+        let instances = cascade(&cards, Overflow::Error).unwrap();
+        assert_eq!(instances, [1, 14, 8, 4, 2, 1]);
+    }
+
+    #[test]
+    fn overflow_policies() {
+        // card 2 wins cards 3 and 4, but card 3 is missing
+        let cards = parse_input("Card 1: 1 | 1\nCard 2: 1 2 | 1 2\nCard 4: 1 | 2").unwrap();
+        assert!(cascade(&cards, Overflow::Error).is_err());
+        assert_eq!(cascade(&cards, Overflow::Clamp).unwrap(), [1, 2, 3]);
+        assert_eq!(cascade(&cards, Overflow::Ignore).unwrap(), [1, 2, 1]);
+
+        // card 1 wins cards 2 and 3, past the end of the table
+        let cards = parse_input("Card 1: 1 2 | 1 2\nCard 2: 1 | 2").unwrap();
+        assert_eq!(cascade(&cards, Overflow::Clamp).unwrap(), [1, 2]);
+        assert_eq!(cascade(&cards, Overflow::Ignore).unwrap(), [1, 1]);
+
+        let cards = parse_input("Card 1: 1 | 1\nCard 1: 1 | 2").unwrap();
+        assert!(cascade(&cards, Overflow::Clamp).is_err());
+
+        // the last card wins a copy of a card past usize::MAX
+        let cards = parse_input(&format!("Card {}: 1 | 1", usize::MAX)).unwrap();
+        assert!(cascade(&cards, Overflow::Error).is_err());
+        assert_eq!(cascade(&cards, Overflow::Clamp).unwrap(), [1]);
+    }
+
+    #[test]
+    fn points_of_many_matches() {
+        let card = |matches| Card {
+            number: 1,
+            winning_numbers: (0..matches).collect(),
+            numbers: (0..matches).collect(),
+        };
+        assert_eq!(card(64).points().unwrap(), 1 << 63);
+        assert!(card(65).points().is_err());
+    }
+}
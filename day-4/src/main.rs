@@ -1,73 +1,89 @@
-use std::{collections::HashSet, env};
+use std::{env, fs, io, path::PathBuf};
 
 use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
 use clap::Parser;
-use regex::Regex;
+use day_4::{cascade, parse_input, provenance, to_dot, Overflow};
 
 /// Scratchcards
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// The puzzle input
+    /// The puzzle input, or - to read it from stdin
     #[arg()]
     input: Option<String>,
 
     /// Advent of code session token
     #[arg(short, long)]
     session: Option<String>,
+
+    /// What to do when a card wins copies of cards that are not in the table: error, clamp or ignore
+    #[arg(short, long, default_value = "error")]
+    overflow: Overflow,
+
+    /// List the matching numbers, points, instances and the sources of the copies of every card
+    #[arg(short, long)]
+    report: bool,
+
+    /// Write the graph of which cards won copies of which cards to a file, in the DOT language
+    #[arg(long)]
+    dot: Option<PathBuf>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let session = args.session.or_else(|| env::var("SESSION").ok());
 
-    let input = if let Some(s) = args.input {
-        s
-    } else {
-        get_puzzle_input(2023, 4, session).context("Could not retrieve puzzle input")?
+    let input = match args.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        None => get_puzzle_input(2023, 4, session).context("Could not retrieve puzzle input")?,
     };
 
-    let re = Regex::new(r"Card\s+(\d+): (.*)\s+\|\s+(.*)").unwrap();
-    let mut sum_part_one = 0;
-
-    // array of (matches, instances) pairs
-    let mut v = Vec::new();
-    for line in input.lines() {
-        let (_m, [_card_number, n1, n2]) = re.captures(line).context("should match")?.extract();
-        let mut winning_set = HashSet::new();
-        let mut count = 0;
+    let cards = parse_input(&input)?;
 
-        for number in n1.split_ascii_whitespace() {
-            let n = u32::from_str_radix(number, 10)?;
-            winning_set.insert(n);
-        }
-        for number in n2.split_ascii_whitespace() {
-            let n = u32::from_str_radix(number, 10)?;
-            if winning_set.contains(&n) {
-                count += 1;
-            }
-        }
-        if count > 0 {
-            sum_part_one = sum_part_one + 2u32.pow(count - 1);
-        }
-        // for part two, add the count as the number of matches
-        // and a single instance (the original copy)
-        v.push((count as usize, 1usize));
-    }
+    let sum_part_one = cards.iter().try_fold(0u64, |sum, card| {
+        sum.checked_add(card.points()?)
+            .context("Too many points to count")
+    })?;
     println!("part one: {}", sum_part_one);
 
-    for index in 0..v.len() {
-        let (matches, instances) = v[index];
-        for _copy in 0..instances {
-            for i in 0..matches {
-                let old = v
-                    .get_mut(index + i + 1)
-                    .context("Puzzle input should not exceed table length")?;
-                old.1 += 1;
-            }
+    let instances = cascade(&cards, args.overflow)?;
+    println!("part two: {}", instances.iter().sum::<u64>());
+
+    if !args.report && args.dot.is_none() {
+        return Ok(());
+    }
+    let provenance = provenance(&cards, args.overflow)?;
+    if args.report {
+        for card in provenance.iter() {
+            let matching = card
+                .matching_numbers
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<Vec<_>>();
+            let sources = card
+                .sources
+                .iter()
+                .map(|(source, copies)| format!("{} from card {}", copies, source))
+                .collect::<Vec<_>>();
+            println!(
+                "Card {}: matched [{}] for {} points, {} instances, copies: {}",
+                card.number,
+                matching.join(" "),
+                card.points,
+                card.instances,
+                if sources.is_empty() {
+                    "none".to_string()
+                } else {
+                    sources.join(", ")
+                }
+            );
         }
     }
-    println!("part two: {}", v.iter().map(|x| x.1).sum::<usize>());
+    if let Some(path) = args.dot {
+        fs::write(&path, to_dot(&provenance))
+            .with_context(|| format!("Could not write {}", path.display()))?;
+    }
     Ok(())
 }
@@ -0,0 +1,67 @@
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use day_5::Range;
+
+/// Seeds
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None, args_conflicts_with_subcommands = true)]
+pub struct Args {
+    #[command(flatten)]
+    pub puzzle: Puzzle,
+
+    #[command(subcommand)]
+    pub query: Option<Query>,
+}
+
+#[derive(ClapArgs, Debug)]
+pub struct Puzzle {
+    /// The puzzle input, or - to read it from stdin
+    #[arg()]
+    pub input: Option<String>,
+
+    /// Advent of code session token
+    #[arg(short, long)]
+    pub session: Option<String>,
+
+    /// The category the maps lead to from the seeds
+    #[arg(short, long, default_value = "location")]
+    pub target: String,
+
+    /// Reject almanacs with overlapping, overflowing or empty entries or empty maps,
+    /// instead of only warning about them
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Query {
+    /// The seeds of the seed ranges that produce targets in a range, locations by default
+    Seeds {
+        /// The targets, as start..end or a single value
+        locations: Range,
+
+        /// Every seed that produces these targets, not only those of the seed ranges
+        #[arg(long)]
+        all: bool,
+
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+
+    /// Every issue of every map, also the harmless ones like gaps
+    Check {
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+
+    /// The maps that lead from the seeds to the target
+    Chain {
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+
+    /// The lowest target reachable from every seed range, the lowest location by default
+    Lowest {
+        #[command(flatten)]
+        puzzle: Puzzle,
+    },
+}
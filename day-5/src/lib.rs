@@ -0,0 +1,391 @@
+pub mod piecewise;
+pub mod validate;
+
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use anyhow::{bail, Context, Error, Result};
+use regex::Regex;
+
+/// The category of the seeds, where every chain of maps starts
+pub const SEED: &str = "seed";
+
+/// A `from-to-to map:` section, with the (dest, source, len) entries of the map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub from: String,
+    pub to: String,
+    pub entries: Vec<(u64, u64, u64)>,
+}
+
+impl Display for Section {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-to-{}", self.from, self.to)
+    }
+}
+
+/// The seeds, and every map section in the order of the input
+#[derive(Debug, Clone)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub sections: Vec<Section>,
+}
+
+impl Almanac {
+    /// The sections that lead from [`SEED`] to `target`, in order.
+    ///
+    /// The sections form a graph of categories, which may branch as long as a single
+    /// route leads to `target`. Cycles on the way are an error, like no route or several routes.
+    pub fn chain(&self, target: &str) -> Result<Vec<&Section>> {
+        let mut routes = HashMap::new();
+        let route = self
+            .route(SEED, target, &mut routes)?
+            .with_context(|| format!("No maps lead from {} to {}", SEED, target))?;
+        Ok(route.into_iter().map(|i| &self.sections[i]).collect())
+    }
+
+    /// The only route of section indices from `category` to `target`, remembered in `routes`.
+    /// A category that is still being looked at has no route yet, reaching it again is a cycle.
+    fn route<'a>(
+        &'a self,
+        category: &'a str,
+        target: &str,
+        routes: &mut HashMap<&'a str, Option<Option<Vec<usize>>>>,
+    ) -> Result<Option<Vec<usize>>> {
+        if category == target {
+            return Ok(Some(vec![]));
+        }
+        match routes.get(category) {
+            Some(Some(route)) => return Ok(route.clone()),
+            Some(None) => bail!("The maps form a cycle through {}", category),
+            None => {}
+        }
+        routes.insert(category, None);
+
+        let mut found: Option<Vec<usize>> = None;
+        for (i, section) in self.sections.iter().enumerate() {
+            if section.from != category {
+                continue;
+            }
+            let Some(rest) = self.route(&section.to, target, routes)? else {
+                continue;
+            };
+            let route = [i].into_iter().chain(rest).collect::<Vec<_>>();
+            if let Some(other) = found {
+                bail!(
+                    "Both {} and {} lead from {} to {}",
+                    self.describe(&other),
+                    self.describe(&route),
+                    category,
+                    target
+                );
+            }
+            found = Some(route);
+        }
+        routes.insert(category, Some(found.clone()));
+        Ok(found)
+    }
+
+    /// A route as the categories it passes, like `seed -> soil -> water`
+    fn describe(&self, route: &[usize]) -> String {
+        let mut categories = vec![self.sections[route[0]].from.as_str()];
+        categories.extend(route.iter().map(|i| self.sections[*i].to.as_str()));
+        categories.join(" -> ")
+    }
+}
+
+/// Parse the seeds and every `from-to-to map:` section, whatever the categories and their order.
+/// Every category is mapped to another one by atmost one section.
+pub fn parse_input(input: &str) -> Result<Almanac> {
+    let seeds = parse_seeds(input)?;
+    let header = Regex::new(r"^(\S+?)-to-(\S+) map:$")?;
+
+    let mut sections: Vec<Section> = vec![];
+    for line in input.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with("seeds:") {
+            continue;
+        }
+        if let Some(captures) = header.captures(line) {
+            let (_full, [from, to]) = captures.extract();
+            if sections.iter().any(|s| s.from == from && s.to == to) {
+                bail!("The {}-to-{} map should appear only once", from, to);
+            }
+            sections.push(Section {
+                from: from.to_string(),
+                to: to.to_string(),
+                entries: vec![],
+            });
+            continue;
+        }
+        let section = sections
+            .last_mut()
+            .with_context(|| format!("Expected a map header, got: {}", line))?;
+        let entry = parse_entry(line).with_context(|| format!("In the {} map", section))?;
+        section.entries.push(entry);
+    }
+
+    Ok(Almanac { seeds, sections })
+}
+
+/// Parse a single `dest source len` entry of a map
+fn parse_entry(line: &str) -> Result<(u64, u64, u64)> {
+    let numbers = line
+        .split_ascii_whitespace()
+        .map(|number| {
+            number
+                .parse()
+                .with_context(|| format!("Expected a number, got: {}", number))
+        })
+        .collect::<Result<Vec<u64>>>()?;
+    match numbers[..] {
+        [dest, source, len] => Ok((dest, source, len)),
+        _ => bail!(
+            "Expected a destination, a source and a length, got: {}",
+            line
+        ),
+    }
+}
+
+pub fn parse_seeds(input: &str) -> Result<Vec<u64>> {
+    let re = Regex::new(r"seeds: (.*)\n")?;
+
+    let (_full_match, [seeds_match]) = re
+        .captures(input)
+        .context("Puzzle should contain a list of seeds!")?
+        .extract();
+
+    let mut seeds = vec![];
+    for seed in seeds_match.split_ascii_whitespace() {
+        let seed = seed.parse().context("Seeds can only be numbers!")?;
+        seeds.push(seed);
+    }
+    Ok(seeds)
+}
+
+/// The seeds read as pairs of a start and a length, for part two
+pub fn seed_ranges(seeds: &[u64]) -> Result<Vec<Range>> {
+    seeds
+        .chunks(2)
+        .map(|pair| match pair {
+            [start, len] => {
+                let end = start
+                    .checked_add(*len)
+                    .context("Seed range runs past u64::MAX")?;
+                Ok(Range::new(*start, end))
+            }
+            _ => bail!("Seeds should come in pairs!"),
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl Range {
+    pub fn new(start: u64, end: u64) -> Range {
+        Range { start, end }
+    }
+    // Compute the intersection of two ranges
+    // Returns None if ranges do not overlap.
+    // Ranges are half-open, so ranges that merely touch do not overlap.
+    pub fn intersection(&self, other: &Range) -> Option<Range> {
+        if self.start >= other.end || self.end <= other.start {
+            None
+        } else {
+            Some(Range {
+                start: u64::max(self.start, other.start),
+                end: u64::min(self.end, other.end),
+            })
+        }
+    }
+}
+
+/// The values covered by any of `ranges`, as sorted ranges that neither overlap nor touch
+pub fn merge_ranges(mut ranges: Vec<Range>) -> Vec<Range> {
+    ranges.sort_unstable_by_key(|range| range.start);
+    let mut merged: Vec<Range> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if last.end >= range.start => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+impl Display for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Parses a half-open range like `10..20`, or a single value like `15` as `15..16`
+impl FromStr for Range {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parse = |v: &str| {
+            v.trim()
+                .parse::<u64>()
+                .with_context(|| format!("Expected start..end, got: {}", s))
+        };
+        let range = match s.split_once("..") {
+            Some((start, end)) => Range::new(parse(start)?, parse(end)?),
+            None => {
+                let value = parse(s)?;
+                Range::new(value, value.checked_add(1).context("Value is too large")?)
+            }
+        };
+        if range.start >= range.end {
+            bail!("Expected a range that is not empty, got: {}", s);
+        }
+        Ok(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use piecewise::PiecewiseMap;
+    use proptest::prelude::*;
+
+    /// The maps of the sections that lead from [`SEED`] to `target`
+    fn maps(almanac: &Almanac, target: &str) -> Vec<Vec<(u64, u64, u64)>> {
+        let chain = almanac.chain(target).unwrap();
+        chain
+            .into_iter()
+            .map(|section| section.entries.clone())
+            .collect()
+    }
+
+    /// Walk a single seed through every map, the first matching entry of a map wins.
+    fn seed_to_location(seed: u64, maps: &[Vec<(u64, u64, u64)>]) -> u64 {
+        let mut next = seed;
+        for map in maps.iter() {
+            for (dest, source, len) in map.iter() {
+                if (*source..(source + len)).contains(&next) {
+                    next = dest + (next - source);
+                    break;
+                }
+            }
+        }
+        next
+    }
+
+    /// A single map with a handful of small, possibly overlapping or touching entries
+    fn map() -> impl Strategy<Value = Vec<(u64, u64, u64)>> {
+        prop::collection::vec((0..50u64, 0..50u64, 1..10u64), 0..5)
+    }
+
+    /// Seed ranges as `(start, end)` pairs
+    fn seed_ranges() -> impl Strategy<Value = Vec<(u64, u64)>> {
+        prop::collection::vec((0..60u64, 1..10u64), 1..4).prop_map(|v| {
+            v.into_iter()
+                .map(|(start, len)| (start, start + len))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn discovers_sections() {
+        let input = "seeds: 1 5
+
+water-to-light map:
+10 0 5
+
+seed-to-soil map:
+0 1 1
+3 2 2
+
+soil-to-water map:
+
+seed-to-moon map:
+7 7 7
+";
+        let almanac = parse_input(input).unwrap();
+        assert_eq!(almanac.sections.len(), 4);
+        let chain = almanac.chain("light").unwrap();
+        let names = chain.iter().map(|section| section.to_string());
+        assert_eq!(
+            names.collect::<Vec<_>>(),
+            ["seed-to-soil", "soil-to-water", "water-to-light"]
+        );
+        let maps = maps(&almanac, "light");
+        assert_eq!(seed_to_location(1, &maps), 10);
+        assert_eq!(seed_to_location(5, &maps), 5);
+        assert!(almanac.chain("seed").unwrap().is_empty());
+
+        let error = almanac.chain("location").unwrap_err();
+        assert_eq!(error.to_string(), "No maps lead from seed to location");
+        let cycle = parse_input(&format!("{}\nlight-to-soil map:\n", input)).unwrap();
+        let error = cycle.chain("moon").unwrap_err();
+        assert_eq!(error.to_string(), "The maps form a cycle through soil");
+        assert!(parse_input(&format!("{}\nseed-to-soil map:\n", input)).is_err());
+        assert!(parse_input("seeds: 1\n1 2 3\n").is_err());
+        assert!(parse_input("seeds: 1\na-to-b map:\n1 2\n").is_err());
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!("10..20".parse::<Range>().unwrap(), Range::new(10, 20));
+        assert_eq!("15".parse::<Range>().unwrap(), Range::new(15, 16));
+        assert!("20..10".parse::<Range>().is_err());
+        assert!("a..b".parse::<Range>().is_err());
+        assert_eq!(
+            super::seed_ranges(&[79, 14, 55, 13]).unwrap()[1],
+            Range::new(55, 68)
+        );
+        assert!(super::seed_ranges(&[79, 14, 55]).is_err());
+    }
+
+    #[test]
+    fn merges_ranges() {
+        let ranges = [(20, 30), (0, 10), (5, 15), (15, 18), (25, 28)]
+            .map(|(start, end)| Range::new(start, end))
+            .to_vec();
+        assert_eq!(
+            merge_ranges(ranges),
+            [Range::new(0, 18), Range::new(20, 30)]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn chained_map_matches_per_seed_walk(
+            maps in prop::collection::vec(map(), 1..8),
+            ranges in seed_ranges(),
+        ) {
+            let pieces = maps.iter().map(|map| PiecewiseMap::from_entries(map)).collect::<Vec<_>>();
+            let chain = PiecewiseMap::chain(&pieces);
+            for seed in 0..70 {
+                prop_assert_eq!(chain.apply(seed), seed_to_location(seed, &maps));
+            }
+
+            let ranges = ranges.iter().map(|(start, end)| Range::new(*start, *end)).collect::<Vec<_>>();
+            let expected = ranges
+                .iter()
+                .flat_map(|range| range.start..range.end)
+                .map(|seed| seed_to_location(seed, &maps))
+                .min();
+            prop_assert_eq!(chain.lowest(&ranges), expected);
+        }
+
+        #[test]
+        fn preimage_holds_every_seed_that_maps_into_range(
+            maps in prop::collection::vec(map(), 1..8),
+            start in 0..60u64,
+            len in 1..10u64,
+        ) {
+            let pieces = maps.iter().map(|map| PiecewiseMap::from_entries(map)).collect::<Vec<_>>();
+            let locations = Range::new(start, start + len);
+            let seeds = PiecewiseMap::chain(&pieces).preimage(locations);
+            for seed in 0..70 {
+                let location = seed_to_location(seed, &maps);
+                let expected = (locations.start..locations.end).contains(&location);
+                prop_assert_eq!(seeds.iter().any(|r| (r.start..r.end).contains(&seed)), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,241 @@
+//! A whole chain of almanac maps as a single map.
+
+use crate::Range;
+
+/// The values from `start` up to `end` map to `dest` and up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub start: u64,
+    pub end: u64,
+    pub dest: u64,
+}
+
+impl Segment {
+    fn apply(&self, value: u64) -> u64 {
+        self.dest + (value - self.start)
+    }
+
+    /// Whether `next` continues this segment, so both can be a single segment
+    fn continues_with(&self, next: &Segment) -> bool {
+        self.end == next.start && self.apply(self.end - 1) + 1 == next.dest
+    }
+}
+
+/// A map that shifts sorted, non-overlapping ranges of values, every other value maps to itself.
+/// Values are below `u64::MAX`, which always maps to itself.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PiecewiseMap {
+    /// Sorted by start, never overlapping
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    /// The map of `(dest, source, len)` entries, where the first entry that contains a value wins.
+    /// Entries are cut short where their source or destination would run past `u64::MAX`.
+    pub fn from_entries(entries: &[(u64, u64, u64)]) -> Self {
+        let mut segments: Vec<Segment> = vec![];
+        for (dest, source, len) in entries.iter().copied() {
+            let len = len.min(u64::MAX - source).min(u64::MAX - dest);
+            let entry = Segment {
+                start: source,
+                end: source + len,
+                dest,
+            };
+            // only the values no earlier entry contains
+            let mut start = entry.start;
+            let mut uncovered = vec![];
+            for segment in segments.iter() {
+                if segment.end <= start || segment.start >= entry.end {
+                    continue;
+                }
+                if segment.start > start {
+                    uncovered.push((start, segment.start));
+                }
+                start = start.max(segment.end);
+            }
+            if start < entry.end {
+                uncovered.push((start, entry.end));
+            }
+            segments.extend(uncovered.into_iter().map(|(start, end)| Segment {
+                start,
+                end,
+                dest: entry.apply(start),
+            }));
+            segments.sort_unstable_by_key(|segment| segment.start);
+        }
+        Self::merged(segments)
+    }
+
+    /// Sorted, non-overlapping `segments`, with the segments that continue each other merged
+    fn merged(segments: Vec<Segment>) -> Self {
+        let mut merged: Vec<Segment> = Vec::with_capacity(segments.len());
+        for segment in segments.into_iter().filter(|s| s.start < s.end) {
+            match merged.last_mut() {
+                Some(last) if last.continues_with(&segment) => last.end = segment.end,
+                _ => merged.push(segment),
+            }
+        }
+        Self { segments: merged }
+    }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The value `value` maps to, found by binary search
+    pub fn apply(&self, value: u64) -> u64 {
+        let i = self.segments.partition_point(|s| s.end <= value);
+        match self.segments.get(i) {
+            Some(segment) if segment.start <= value => segment.apply(value),
+            _ => value,
+        }
+    }
+
+    /// The values of `range` cut where the map has a breakpoint,
+    /// as segments that also include the values that map to themselves
+    pub fn pieces(&self, range: Range) -> Vec<Segment> {
+        let mut pieces = vec![];
+        let mut start = range.start;
+        let first = self.segments.partition_point(|s| s.end <= start);
+        for segment in self.segments[first..].iter() {
+            if start >= range.end || segment.start >= range.end {
+                break;
+            }
+            if segment.start > start {
+                pieces.push(Segment {
+                    start,
+                    end: segment.start,
+                    dest: start,
+                });
+                start = segment.start;
+            }
+            let end = segment.end.min(range.end);
+            pieces.push(Segment {
+                start,
+                end,
+                dest: segment.apply(start),
+            });
+            start = end;
+        }
+        if start < range.end {
+            pieces.push(Segment {
+                start,
+                end: range.end,
+                dest: start,
+            });
+        }
+        pieces
+    }
+
+    /// Every value that maps into `range`, as sorted ranges that neither overlap nor touch.
+    /// Several ranges can map onto the same values, so there may be more than one.
+    pub fn preimage(&self, range: Range) -> Vec<Range> {
+        let mut ranges = vec![];
+        for piece in self.pieces(Range::new(0, u64::MAX)) {
+            let image = Range::new(piece.dest, piece.apply(piece.end - 1) + 1);
+            if let Some(hit) = image.intersection(&range) {
+                ranges.push(Range::new(
+                    piece.start + (hit.start - piece.dest),
+                    piece.start + (hit.end - piece.dest),
+                ));
+            }
+        }
+        // pieces are sorted already, merge the ones that touch
+        let mut merged: Vec<Range> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if last.end == range.start => last.end = range.end,
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    /// The map that first applies this map and then `next`
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut segments = vec![];
+        for piece in self.pieces(Range::new(0, u64::MAX)) {
+            // the piece maps onto a single range, which `next` may cut up further
+            let image = Range::new(piece.dest, piece.apply(piece.end - 1) + 1);
+            for cut in next.pieces(image) {
+                segments.push(Segment {
+                    start: piece.start + (cut.start - piece.dest),
+                    end: piece.start + (cut.end - piece.dest),
+                    dest: cut.dest,
+                });
+            }
+        }
+        // drop the segments that map to themselves, they are implied
+        segments.retain(|segment| segment.start != segment.dest);
+        Self::merged(segments)
+    }
+
+    /// All `maps` applied one after another as a single map
+    pub fn chain<'a>(maps: impl IntoIterator<Item = &'a PiecewiseMap>) -> PiecewiseMap {
+        maps.into_iter()
+            .fold(PiecewiseMap::default(), |chain, map| chain.then(map))
+    }
+
+    /// The lowest value any value of `ranges` maps to, None when all ranges are empty.
+    /// Within a piece values map in order, so only the start of every piece needs to be looked at.
+    pub fn lowest(&self, ranges: &[Range]) -> Option<u64> {
+        ranges
+            .iter()
+            .flat_map(|range| self.pieces(*range))
+            .map(|piece| piece.dest)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_entry_wins() {
+        let map = PiecewiseMap::from_entries(&[(100, 10, 5), (200, 12, 10), (12, 12, 5)]);
+        assert_eq!(map.apply(9), 9);
+        assert_eq!(map.apply(11), 101);
+        assert_eq!(map.apply(15), 203);
+        assert_eq!(map.apply(22), 22);
+        assert_eq!(map.segments().len(), 2);
+    }
+
+    #[test]
+    fn composes_and_merges() {
+        let a = PiecewiseMap::from_entries(&[(10, 0, 10)]);
+        let b = PiecewiseMap::from_entries(&[(0, 10, 10), (20, 0, 10)]);
+        // a then b sends 0..10 back onto itself, only 10..20 moves
+        let ab = a.then(&b);
+        assert_eq!(
+            ab.segments(),
+            [Segment {
+                start: 10,
+                end: 20,
+                dest: 0
+            }]
+        );
+        let ba = b.then(&a);
+        assert_eq!(
+            ba.segments(),
+            [Segment {
+                start: 0,
+                end: 10,
+                dest: 20
+            }]
+        );
+        assert_eq!(ba.lowest(&[Range::new(5, 15)]), Some(10));
+        assert_eq!(ba.apply(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn preimages() {
+        // 0..10 and 20..30 both end up on 20..30, nothing ends up on 30..35
+        let map = PiecewiseMap::from_entries(&[(20, 0, 10), (40, 30, 5)]);
+        assert_eq!(
+            map.preimage(Range::new(25, 45)),
+            [Range::new(5, 10), Range::new(25, 45)]
+        );
+        assert_eq!(map.preimage(Range::new(30, 35)), []);
+    }
+}
@@ -0,0 +1,218 @@
+//! Checks for almanacs whose maps are not what they seem.
+
+use std::fmt::Display;
+
+use crate::{Almanac, Range, Section};
+
+/// Something off about a single map, entries count from 1 in the order of the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+    /// The map has no entries, it maps every value to itself
+    Empty,
+    /// An entry of length 0, which maps nothing
+    ZeroLength { entry: usize },
+    /// The source or destination of an entry runs past `u64::MAX`
+    Overflow { entry: usize },
+    /// Two entries both map `values`, the first one wins
+    Overlap {
+        first: usize,
+        second: usize,
+        values: Range,
+    },
+    /// Two entries continue each other in both source and destination
+    Mergeable { first: usize, second: usize },
+    /// No entry maps `values`, between the lowest and the highest source
+    Gap(Range),
+}
+
+impl Issue {
+    /// Whether the map may not do what was meant. Mergeable entries and gaps are harmless,
+    /// puzzle inputs have plenty of them.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Issue::Mergeable { .. } | Issue::Gap(_))
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Empty => write!(f, "the map has no entries"),
+            Issue::ZeroLength { entry } => write!(f, "entry {} has length 0", entry),
+            Issue::Overflow { entry } => write!(f, "entry {} runs past u64::MAX", entry),
+            Issue::Overlap {
+                first,
+                second,
+                values,
+            } => write!(f, "entries {} and {} overlap on {}", first, second, values),
+            Issue::Mergeable { first, second } => {
+                write!(
+                    f,
+                    "entries {} and {} could be a single entry",
+                    first, second
+                )
+            }
+            Issue::Gap(values) => write!(f, "no entry maps {}", values),
+        }
+    }
+}
+
+/// An issue with one of the sections of an almanac
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The name of the section, like `seed-to-soil`
+    pub section: String,
+    pub issue: Issue,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} map: {}", self.section, self.issue)
+    }
+}
+
+/// Every issue of every section, section by section
+pub fn validate(almanac: &Almanac) -> Vec<Diagnostic> {
+    almanac
+        .sections
+        .iter()
+        .flat_map(|section| {
+            validate_section(section)
+                .into_iter()
+                .map(|issue| Diagnostic {
+                    section: section.to_string(),
+                    issue,
+                })
+        })
+        .collect()
+}
+
+/// Every issue of a single section. Entries that overflow are cut off at `u64::MAX`
+/// when looking for overlaps and gaps, like [`crate::piecewise::PiecewiseMap::from_entries`] does.
+pub fn validate_section(section: &Section) -> Vec<Issue> {
+    if section.entries.is_empty() {
+        return vec![Issue::Empty];
+    }
+
+    let mut issues = vec![];
+    // (entry, source range, dest) of every entry that maps something
+    let mut sources = vec![];
+    for (i, (dest, source, len)) in section.entries.iter().copied().enumerate() {
+        let entry = i + 1;
+        if len == 0 {
+            issues.push(Issue::ZeroLength { entry });
+            continue;
+        }
+        if source.checked_add(len).is_none() || dest.checked_add(len).is_none() {
+            issues.push(Issue::Overflow { entry });
+        }
+        let end = source.saturating_add(len);
+        sources.push((entry, Range::new(source, end), dest));
+    }
+
+    // sweep the sources from low to high, every entry overlaps the entries still active at its
+    // start, gaps and merges are against the entry that reaches the furthest
+    sources.sort_unstable_by_key(|(entry, range, _)| (range.start, *entry));
+    let mut active: Vec<(usize, Range)> = vec![];
+    let mut furthest: Option<(usize, Range, u64)> = None;
+    for (entry, range, dest) in sources {
+        active.retain(|(_, other)| other.end > range.start);
+        for (other, values) in active
+            .iter()
+            .filter_map(|(other, other_range)| Some((*other, range.intersection(other_range)?)))
+        {
+            issues.push(Issue::Overlap {
+                first: other.min(entry),
+                second: other.max(entry),
+                values,
+            });
+        }
+        active.push((entry, range));
+
+        if let Some((previous, reach, previous_dest)) = furthest {
+            if range.start > reach.end {
+                issues.push(Issue::Gap(Range::new(reach.end, range.start)));
+            } else if range.start == reach.end
+                && previous_dest.checked_add(reach.end - reach.start) == Some(dest)
+            {
+                issues.push(Issue::Mergeable {
+                    first: previous,
+                    second: entry,
+                });
+            }
+            if reach.end >= range.end {
+                continue;
+            }
+        }
+        furthest = Some((entry, range, dest));
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    #[test]
+    fn finds_every_issue() {
+        let almanac = parse_input(
+            "seeds: 1
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-water map:
+0 10 5
+5 15 5
+20 30 5
+0 32 10
+1 1 0
+0 18446744073709551610 10
+
+water-to-light map:
+",
+        )
+        .unwrap();
+        let diagnostics = validate(&almanac);
+        let messages = diagnostics
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        let errors = diagnostics.iter().filter(|d| d.issue.is_error());
+        assert_eq!(errors.count(), 4);
+        assert_eq!(
+            messages,
+            [
+                "soil-to-water map: entry 5 has length 0",
+                "soil-to-water map: entry 6 runs past u64::MAX",
+                "soil-to-water map: entries 1 and 2 could be a single entry",
+                "soil-to-water map: no entry maps 20..30",
+                "soil-to-water map: entries 3 and 4 overlap on 32..35",
+                "soil-to-water map: no entry maps 42..18446744073709551610",
+                "water-to-light map: the map has no entries",
+            ]
+        );
+    }
+
+    #[test]
+    fn overlaps_of_entries_that_do_not_reach_furthest() {
+        let section = Section {
+            from: "seed".to_string(),
+            to: "soil".to_string(),
+            entries: vec![(0, 0, 100), (0, 10, 10), (0, 15, 10)],
+        };
+        let overlaps = validate_section(&section)
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            overlaps,
+            [
+                "entries 1 and 2 overlap on 10..20",
+                "entries 1 and 3 overlap on 15..25",
+                "entries 2 and 3 overlap on 15..20",
+            ]
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Error, Result};
+use num::{integer::Roots, BigInt, Integer, ToPrimitive};
+use regex::Regex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race<T = u64> {
+    pub time: T,
+    pub record_dist: T,
+}
+
+impl<T: Integer + Clone> Race<T> {
+    /// The hold times that travel the furthest, the same one twice when the time is even
+    pub fn optimal_holds(&self) -> (T, T) {
+        let half = self.time.clone() / (T::one() + T::one());
+        (half.clone(), self.time.clone() - half)
+    }
+}
+
+impl Race {
+    /// The first and last hold time that beat the record, None when no hold time does
+    pub fn winning_holds(&self) -> Option<(u64, u64)> {
+        self.winning_holds_at(1)
+    }
+
+    /// Like [`Race::winning_holds`], when every millisecond of holding adds `acceleration`
+    /// millimeters per millisecond to the speed of the boat
+    pub fn winning_holds_at(&self, acceleration: u64) -> Option<(u64, u64)> {
+        // acceleration * x > record_dist exactly when x > record_dist / acceleration
+        let record_dist = self.record_dist.checked_div(acceleration)?;
+        let (first, last) = winning_holds_u128(self.time as u128, record_dist as u128)?;
+        // both are atmost the time
+        Some((first as u64, last as u64))
+    }
+
+    /// The number of hold times that beat the record
+    pub fn ways_to_win(&self) -> u64 {
+        self.ways_to_win_at(1)
+    }
+
+    pub fn ways_to_win_at(&self, acceleration: u64) -> u64 {
+        self.winning_holds_at(acceleration)
+            .map_or(0, |(first, last)| last - first + 1)
+    }
+}
+
+impl Race<BigInt> {
+    /// The first and last hold time that beat the record, None when no hold time does
+    pub fn winning_holds(&self) -> Option<(BigInt, BigInt)> {
+        self.winning_holds_at(1)
+    }
+
+    /// Like [`Race::winning_holds`], when every millisecond of holding adds `acceleration`
+    /// millimeters per millisecond to the speed of the boat
+    pub fn winning_holds_at(&self, acceleration: u64) -> Option<(BigInt, BigInt)> {
+        if acceleration == 0 {
+            return None;
+        }
+        winning_holds(&self.time, &(&self.record_dist / acceleration))
+    }
+
+    /// The number of hold times that beat the record
+    pub fn ways_to_win(&self) -> BigInt {
+        self.ways_to_win_at(1)
+    }
+
+    pub fn ways_to_win_at(&self, acceleration: u64) -> BigInt {
+        self.winning_holds_at(acceleration)
+            .map_or(BigInt::from(0), |(first, last)| last - first + 1)
+    }
+}
+
+/// The first and last hold time out of `0..=time` that travel further than `record_dist`,
+/// None when no hold time does. `time * time` should fit in `T`.
+///
+/// Holding for `h` travels `h * (time - h)`, which beats the record between the roots of
+/// `h^2 - time * h + record_dist`, at `(time ± sqrt(time^2 - 4 * record_dist)) / 2`.
+/// The integer square root rounds down, so the first root is corrected by a step at most,
+/// the last one mirrors it around `time / 2`.
+pub fn winning_holds<T>(time: &T, record_dist: &T) -> Option<(T, T)>
+where
+    T: Integer + Roots + Clone,
+{
+    let two = T::one() + T::one();
+    let wins = |hold: &T| hold.clone() * (time.clone() - hold.clone()) > *record_dist;
+
+    // the furthest anyone gets is by holding half of the time
+    let half = time.clone() / two.clone();
+    if !wins(&half) {
+        return None;
+    }
+
+    // half wins, so time^2 / 4 > record_dist and the discriminant is positive
+    let discriminant =
+        time.clone() * time.clone() - (two.clone() + two.clone()) * record_dist.clone();
+    let mut first = (time.clone() - discriminant.sqrt()) / two;
+    while !wins(&first) {
+        first = first + T::one();
+    }
+    while first > T::zero() && wins(&(first.clone() - T::one())) {
+        first = first - T::one();
+    }
+    let last = time.clone() - first.clone();
+    Some((first, last))
+}
+
+/// Like [`winning_holds`] for any `u128`, falling back to a [`BigInt`] when `time^2` is too large
+pub fn winning_holds_u128(time: u128, record_dist: u128) -> Option<(u128, u128)> {
+    if time.checked_mul(time).is_some() {
+        return winning_holds(&time, &record_dist);
+    }
+    let (first, last) = winning_holds(&BigInt::from(time), &BigInt::from(record_dist))?;
+    // both are atmost the time
+    Some((first.to_u128()?, last.to_u128()?))
+}
+
+/// Parse the races of part one,
+/// and the single race of part two where the spaces between the numbers are ignored.
+pub fn parse_input(input: &str) -> Result<(Vec<Race>, Race<BigInt>)> {
+    let re = Regex::new(r"Time:\s+(.*)\nDistance:\s+(.*)")?;
+    let (_m, [time, dist]) = re.captures(input).context("should match")?.extract();
+    let race_times: Vec<u64> = time
+        .split_ascii_whitespace()
+        .map(|w| w.parse().context("not a number"))
+        .collect::<Result<Vec<_>>>()?;
+
+    let record_dists: Vec<u64> = dist
+        .split_ascii_whitespace()
+        .map(|w| w.parse().context("not a number"))
+        .collect::<Result<Vec<_>>>()?;
+
+    if race_times.len() != record_dists.len() {
+        bail!("Every race should have a time and a record distance");
+    }
+    let races = race_times
+        .into_iter()
+        .zip(record_dists)
+        .map(|(time, record_dist)| Race { time, record_dist })
+        .collect();
+
+    // part two, the numbers of all races together may not fit in a u64
+    let race = Race {
+        time: time.split_ascii_whitespace().collect::<String>().parse()?,
+        record_dist: dist.split_ascii_whitespace().collect::<String>().parse()?,
+    };
+
+    Ok((races, race))
+}
+
+/// How the races are written down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Any of the formats below, told apart by their first line
+    Auto,
+    /// A `Time:` line and a `Distance:` line, like the puzzle
+    Aoc,
+    /// A time and a record distance on every line
+    Pairs,
+    /// A time and a record distance on every line separated by a comma, optionally below a header
+    /// that names the `time` and `distance` columns
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "auto" => Ok(Format::Auto),
+            "aoc" => Ok(Format::Aoc),
+            "pairs" => Ok(Format::Pairs),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("Expected auto, aoc, pairs or csv, got: {}", s),
+        }
+    }
+}
+
+/// Parse the races of part one and the single race of part two in any format
+pub fn parse_races(input: &str, format: Format) -> Result<(Vec<Race>, Race<BigInt>)> {
+    let format = match format {
+        Format::Auto => {
+            let first = input.lines().find(|line| !line.trim().is_empty());
+            match first.unwrap_or_default() {
+                line if line.starts_with("Time:") => Format::Aoc,
+                line if line.contains(',') => Format::Csv,
+                _ => Format::Pairs,
+            }
+        }
+        format => format,
+    };
+    let races = match format {
+        Format::Aoc | Format::Auto => return parse_input(input),
+        Format::Pairs => parse_pairs(input)?,
+        Format::Csv => parse_csv(input)?,
+    };
+    let race = kerned(&races)?;
+    Ok((races, race))
+}
+
+/// A time and a record distance on every line, separated by whitespace
+pub fn parse_pairs(input: &str) -> Result<Vec<Race>> {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let numbers = line
+                .split_ascii_whitespace()
+                .map(|w| w.parse().context("not a number"))
+                .collect::<Result<Vec<u64>>>()
+                .with_context(|| format!("Invalid race: {}", line))?;
+            match numbers[..] {
+                [time, record_dist] => Ok(Race { time, record_dist }),
+                _ => bail!("Expected a time and a record distance, got: {}", line),
+            }
+        })
+        .collect()
+}
+
+/// A time and a record distance on every line, separated by a comma.
+/// A header can name the columns `time` and `distance` (or `record`) in any order.
+pub fn parse_csv(input: &str) -> Result<Vec<Race>> {
+    let mut lines = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .peekable();
+
+    let (mut time, mut dist) = (0, 1);
+    let header = lines.peek().map(|line| cells(line));
+    if let Some(header) = header.filter(|header| header[0].parse::<u64>().is_err()) {
+        let column = |names: &[&str]| {
+            header
+                .iter()
+                .position(|cell| names.contains(&cell.to_lowercase().as_str()))
+                .with_context(|| format!("The header should have a {} column", names[0]))
+        };
+        time = column(&["time"])?;
+        dist = column(&["distance", "record"])?;
+        lines.next();
+    }
+
+    lines
+        .map(|line| {
+            let cells = cells(line);
+            let cell = |i: usize| -> Result<u64> {
+                cells
+                    .get(i)
+                    .with_context(|| format!("Expected atleast {} cells, got: {}", i + 1, line))?
+                    .parse()
+                    .with_context(|| format!("Invalid race: {}", line))
+            };
+            Ok(Race {
+                time: cell(time)?,
+                record_dist: cell(dist)?,
+            })
+        })
+        .collect()
+}
+
+/// The cells of a line of CSV, without surrounding whitespace
+fn cells(line: &str) -> Vec<&str> {
+    line.split(',').map(str::trim).collect()
+}
+
+/// The single race of part two, where the numbers of all races are written as one
+pub fn kerned(races: &[Race]) -> Result<Race<BigInt>> {
+    let time = races
+        .iter()
+        .map(|race| race.time.to_string())
+        .collect::<String>();
+    let dist = races.iter().map(|race| race.record_dist.to_string());
+    Ok(Race {
+        time: time.parse().context("There should be atleast one race")?,
+        record_dist: dist.collect::<String>().parse()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Simulate every hold time, and count the ones that beat the record.
+    fn ways_to_win_by_simulation(time: u64, record_dist: u64) -> u64 {
+        let mut dists = vec![];
+        for i in 0..time {
+            let time_left = time - i;
+            let speed = i;
+            let distance_traveled = speed * time_left;
+            dists.push(distance_traveled);
+        }
+        dists.into_iter().filter(|d| *d > record_dist).count() as u64
+    }
+
+    #[test]
+    fn example() {
+        let (races, race) = parse_input("Time:      7  15   30\nDistance:  9  40  200").unwrap();
+        let ways = races
+            .iter()
+            .map(|race| race.ways_to_win())
+            .collect::<Vec<_>>();
+        assert_eq!(ways, [4, 8, 9]);
+        assert_eq!(races[0].winning_holds(), Some((2, 5)));
+        assert_eq!(race.ways_to_win(), BigInt::from(71503));
+    }
+
+    /// Races far beyond u64, checked at the boundaries of the interval
+    #[test]
+    fn huge_races() {
+        let time = u128::MAX - 6;
+        let record_dist = u128::MAX / 5;
+        let (first, last) = winning_holds_u128(time, record_dist).unwrap();
+        let wins =
+            |hold: u128| BigInt::from(hold) * BigInt::from(time - hold) > BigInt::from(record_dist);
+        assert!(wins(first) && !wins(first - 1));
+        assert!(wins(last) && !wins(last + 1));
+        assert_eq!(first + last, time);
+
+        let time = num::pow(BigInt::from(10), 60) + BigInt::from(1);
+        let record_dist: BigInt = (&time * &time - 1) / 4;
+        // the two hold times around the middle travel exactly the record
+        assert_eq!(winning_holds(&time, &record_dist), None);
+        let race = Race {
+            time: time.clone(),
+            record_dist: record_dist - 1,
+        };
+        assert_eq!(race.ways_to_win(), BigInt::from(2));
+    }
+
+    #[test]
+    fn formats() {
+        let aoc = parse_races(
+            "Time:      7  15   30\nDistance:  9  40  200\n",
+            Format::Auto,
+        )
+        .unwrap();
+        let pairs = parse_races("7 9\n15 40\n\n30 200\n", Format::Auto).unwrap();
+        let csv = parse_races("Distance, Time\n9,7\n40,15\n200,30\n", Format::Auto).unwrap();
+        let bare = parse_races("7,9\n15,40\n30,200", Format::Csv).unwrap();
+        assert_eq!(aoc, pairs);
+        assert_eq!(aoc, csv);
+        assert_eq!(aoc, bare);
+        assert!(parse_races("7 9 1\n", Format::Pairs).is_err());
+        assert!(parse_races("time,speed\n7,9\n", Format::Csv).is_err());
+        assert!("tsv".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn acceleration() {
+        let race = Race {
+            time: 30,
+            record_dist: 200,
+        };
+        // holding 5 travels 2 * 5 * 25 = 250, holding 4 only 2 * 4 * 26 = 208
+        assert_eq!(race.winning_holds_at(2), Some((4, 26)));
+        assert_eq!(race.winning_holds_at(0), None);
+        assert_eq!(race.optimal_holds(), (15, 15));
+        let race = Race {
+            time: BigInt::from(7),
+            record_dist: BigInt::from(9),
+        };
+        assert_eq!(race.ways_to_win_at(3), BigInt::from(6));
+        assert_eq!(race.optimal_holds(), (BigInt::from(3), BigInt::from(4)));
+    }
+
+    proptest! {
+        #[test]
+        fn closed_form_matches_simulation(time in 0..200u64, record_dist in 0..10_000u64) {
+            let race = Race { time, record_dist };
+            prop_assert_eq!(race.ways_to_win(), ways_to_win_by_simulation(time, record_dist));
+        }
+
+        #[test]
+        fn acceleration_matches_simulation(time in 0..200u64, record_dist in 0..10_000u64, acceleration in 1..5u64) {
+            let race = Race { time, record_dist };
+            let expected = (0..=time).filter(|hold| acceleration * hold * (time - hold) > record_dist).count();
+            prop_assert_eq!(race.ways_to_win_at(acceleration), expected as u64);
+        }
+    }
+}
@@ -1,75 +1,93 @@
-use std::env;
+use std::{env, fmt::Display, io};
 
 use anyhow::{Context, Result};
 use aoc_input_lib::get_puzzle_input;
 use clap::Parser;
-use regex::Regex;
+use day_6::{parse_races, Format, Race};
+use num::BigInt;
 
 /// Wait for it
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// The puzzle input
+    /// The puzzle input, or - to read it from stdin
     #[arg()]
     input: Option<String>,
 
     /// Advent of code session token
     #[arg(short, long)]
     session: Option<String>,
+
+    /// How the races are written down: auto, aoc, pairs or csv
+    #[arg(short, long, default_value = "auto")]
+    format: Format,
+
+    /// The speed every millisecond of holding the button adds, in millimeters per millisecond
+    #[arg(short, long, default_value_t = 1)]
+    acceleration: u64,
+
+    /// List the winning hold times and the optimal hold time of every race
+    #[arg(short, long)]
+    report: bool,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let session = args.session.or_else(|| env::var("SESSION").ok());
-    let input = if let Some(s) = args.input {
-        s
-    } else {
-        get_puzzle_input(2023, 6, session).context("Could not retrieve puzzle input!")?
+    let input = match args.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        None => get_puzzle_input(2023, 6, session).context("Could not retrieve puzzle input!")?,
     };
-    dbg!(&input);
-
-    let re = Regex::new(r"Time:\s+(.*)\nDistance:\s+(.*)")?;
-    let (_m, [time, dist]) = re.captures(&input).context("should match")?.extract();
-    let race_times: Vec<u32> = time
-        .split_ascii_whitespace()
-        .map(|w| u32::from_str_radix(w, 10).context("not a number"))
-        .collect::<Result<Vec<_>>>()?;
 
-    let record_dists: Vec<u32> = dist
-        .split_ascii_whitespace()
-        .map(|w| u32::from_str_radix(w, 10).context("not a number"))
-        .collect::<Result<Vec<_>>>()?;
+    let (races, race) = parse_races(&input, args.format)?;
+    let acceleration = args.acceleration;
 
-    let mut sum_part_one = 1;
-    for (index, time) in race_times.iter().copied().enumerate() {
-        let record_dist = *record_dists.get(index).unwrap();
-        // distances recorded by simulation
-        let mut dists = vec![];
-        for i in 0..time {
-            let time_left = time - i;
-            let speed = i;
-            let distance_traveled = speed * time_left;
-            dists.push(distance_traveled);
+    if args.report {
+        for (i, race) in races.iter().enumerate() {
+            let holds = race.winning_holds_at(acceleration);
+            report(
+                &format!("race {}", i + 1),
+                race,
+                holds,
+                race.ways_to_win_at(acceleration),
+            );
         }
-        // looking at this is interesting; the vector is symmetric
-        dbg!(&dists);
-        let margin: usize = dists.into_iter().filter(|d| *d > record_dist).count();
-        sum_part_one *= margin;
+        let holds = race.winning_holds_at(acceleration);
+        report(
+            "kerned race",
+            &race,
+            holds,
+            race.ways_to_win_at(acceleration),
+        );
     }
+
+    let sum_part_one = races
+        .iter()
+        .map(|race| BigInt::from(race.ways_to_win_at(acceleration)))
+        .product::<BigInt>();
     println!("part one: {}", sum_part_one);
 
     // part two
-    let time = u64::from_str_radix(&time.split_ascii_whitespace().collect::<String>(), 10)?;
-    let record_dist = u64::from_str_radix(&dist.split_ascii_whitespace().collect::<String>(), 10)?;
-
-    let mut dists = vec![];
-    for i in 0..time {
-        let time_left = time - i;
-        let speed = i;
-        let distance_traveled = speed * time_left;
-        dists.push(distance_traveled);
-    }
-    let margin: usize = dists.into_iter().filter(|d| *d > record_dist).count();
-    println!("part two: {}", margin);
+    println!("part two: {}", race.ways_to_win_at(acceleration));
     Ok(())
 }
+
+fn report<T>(name: &str, race: &Race<T>, holds: Option<(T, T)>, ways: T)
+where
+    T: Display + num::Integer + Clone,
+{
+    let (low, high) = race.optimal_holds();
+    let optimal = if low == high {
+        low.to_string()
+    } else {
+        format!("{} or {}", low, high)
+    };
+    let holds = holds.map_or("none".to_string(), |(first, last)| {
+        format!("{}..={}", first, last)
+    });
+    println!(
+        "{}: time {}, record {}: winning holds {} ({} ways), optimal hold {}",
+        name, race.time, race.record_dist, holds, ways, optimal
+    );
+}
@@ -0,0 +1,223 @@
+pub mod ruleset;
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use anyhow::{Context, Result};
+use ruleset::{Classification, Ruleset};
+
+/// Parse every hand and its bid, under the rules of part one and part two
+pub fn parse_input(input: &str) -> Result<Vec<(Hand, u64)>> {
+    parse_input_with(input, &[Ruleset::part_one(), Ruleset::part_two()])
+}
+
+/// Parse every hand and its bid, under every one of `rulesets`
+pub fn parse_input_with(input: &str, rulesets: &[Ruleset]) -> Result<Vec<(Hand, u64)>> {
+    let mut words = input.split_ascii_whitespace();
+    let mut hands_and_bids = Vec::new();
+
+    // build vec of hands and their bids
+    while let Some(hand) = words.next() {
+        let bids = words
+            .next()
+            .context("Every hand should have a bid")?
+            .parse()?;
+        hands_and_bids.push((Hand::with_rulesets(hand, rulesets)?, bids));
+    }
+    Ok(hands_and_bids)
+}
+
+/// The cards of a hand, with how strong it is under every ruleset it was made for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hand {
+    pub cards: String,
+    /// The key of the hand under every ruleset, in the order of the rulesets, see [`Ruleset::key`]
+    keys: Vec<u64>,
+}
+
+impl Hand {
+    /// A hand under the rules of part one and part two
+    pub fn new(value: &str) -> Result<Hand> {
+        Self::with_rulesets(value, &[Ruleset::part_one(), Ruleset::part_two()])
+    }
+
+    pub fn with_rulesets(value: &str, rulesets: &[Ruleset]) -> Result<Hand> {
+        let keys = rulesets
+            .iter()
+            .map(|ruleset| ruleset.key(value))
+            .collect::<Result<_>>()?;
+        Ok(Hand {
+            cards: value.to_string(),
+            keys,
+        })
+    }
+
+    /// The key of the hand under the ruleset at index `ruleset`, stronger hands have greater keys
+    pub fn key(&self, ruleset: usize) -> u64 {
+        self.keys[ruleset]
+    }
+
+    /// Compare two hands under the ruleset at index `ruleset`
+    pub fn order(&self, other: &Hand, ruleset: usize) -> Ordering {
+        self.key(ruleset).cmp(&other.key(ruleset))
+    }
+}
+
+/// Rank the hands under the ruleset at index `ruleset`, weakest first,
+/// and add up the bid of every hand times its rank
+pub fn total_winnings(hands_and_bids: &mut [(Hand, u64)], ruleset: usize) -> u64 {
+    // a stable sort, identical hands keep the order of the input
+    hands_and_bids.sort_by_key(|(hand, _bid)| hand.key(ruleset));
+    hands_and_bids
+        .iter()
+        .enumerate()
+        // rank = index + 1
+        .map(|(index, (_hand, bid))| (index as u64 + 1) * bid)
+        .sum()
+}
+
+/// Where a hand ended up under a ruleset, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placement {
+    pub classification: Classification,
+    /// 1 for the weakest hand
+    pub rank: usize,
+    /// The bid times the rank
+    pub winnings: u64,
+}
+
+/// The placement of every hand under the ruleset at index `ruleset` of `rulesets`, in input order.
+/// Identical hands are ranked in input order, like [`total_winnings`] does.
+pub fn placements(
+    hands_and_bids: &[(Hand, u64)],
+    rulesets: &[Ruleset],
+    ruleset: usize,
+) -> Result<Vec<Placement>> {
+    let mut order = (0..hands_and_bids.len()).collect::<Vec<_>>();
+    order.sort_by_key(|i| hands_and_bids[*i].0.key(ruleset));
+    let mut ranks = vec![0; hands_and_bids.len()];
+    for (rank, i) in order.into_iter().enumerate() {
+        ranks[i] = rank + 1;
+    }
+
+    hands_and_bids
+        .iter()
+        .zip(ranks)
+        .map(|((hand, bid), rank)| {
+            Ok(Placement {
+                classification: rulesets[ruleset].classify(&hand.cards)?,
+                rank,
+                winnings: rank as u64 * bid,
+            })
+        })
+        .collect()
+}
+
+/// The indices of hands with the same cards, for every set of identical hands in input order.
+/// Their order decides which one gets which rank.
+pub fn identical_hands(hands_and_bids: &[(Hand, u64)]) -> Vec<Vec<usize>> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (i, (hand, _bid)) in hands_and_bids.iter().enumerate() {
+        match seen.get(hand.cards.as_str()) {
+            Some(group) => groups[*group].push(i),
+            None => {
+                seen.insert(&hand.cards, groups.len());
+                groups.push(vec![i]);
+            }
+        }
+    }
+    groups.retain(|group| group.len() > 1);
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const ALLOWED: &[char] = &[
+        'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+    ];
+
+    /// The category of a hand under the rules of part one, 0 for high card
+    fn kind(hand: &str) -> usize {
+        let ruleset = Ruleset::part_one();
+        ruleset.category_rank(ruleset.key(hand).unwrap())
+    }
+
+    /// Try every other card for every joker, and keep the best part one kind.
+    ///
+    /// Cards that do not occur in the hand are interchangeable,
+    /// so only the cards in the hand and a single absent card are tried.
+    fn kind_by_substitution(hand: &str) -> usize {
+        let Some(joker) = hand.find('J') else {
+            return kind(hand);
+        };
+        let absent = ALLOWED.iter().find(|ch| !hand.contains(**ch)).unwrap();
+        ALLOWED
+            .iter()
+            .filter(|ch| **ch != 'J' && (hand.contains(**ch) || *ch == absent))
+            .map(|ch| {
+                let mut substituted = hand.to_string();
+                substituted.replace_range(joker..joker + 1, &ch.to_string());
+                kind_by_substitution(&substituted)
+            })
+            .max()
+            .unwrap()
+    }
+
+    /// Hands of five cards, with jokers drawn about half of the time
+    fn hand() -> impl Strategy<Value = String> {
+        let card = prop_oneof![Just('J'), prop::sample::select(ALLOWED)];
+        prop::collection::vec(card, 5).prop_map(|v| v.into_iter().collect())
+    }
+
+    #[test]
+    fn example() {
+        let mut hands =
+            parse_input("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483").unwrap();
+        assert_eq!(total_winnings(&mut hands, 0), 6440);
+        assert_eq!(total_winnings(&mut hands, 1), 5905);
+        assert!(Hand::new("AAAA").is_err());
+        assert!(Hand::new("AAAA1").is_err());
+    }
+
+    #[test]
+    fn explanations() {
+        let mut hands = parse_input("KTJJT 220\nQQQJA 483\nKTJJT 1\n32T3K 765").unwrap();
+        let rulesets = [Ruleset::part_one(), Ruleset::part_two()];
+        let placements = placements(&hands, &rulesets, 1).unwrap();
+        let ranks = placements.iter().map(|p| p.rank).collect::<Vec<_>>();
+        assert_eq!(ranks, [3, 2, 4, 1]);
+        assert_eq!(placements[0].classification.category, 1);
+        assert_eq!(placements[0].classification.wildcards, [('T', 2)]);
+        assert_eq!(placements[2].winnings, 4);
+        assert_eq!(identical_hands(&hands), [vec![0, 2]]);
+
+        let total = placements.iter().map(|p| p.winnings).sum::<u64>();
+        assert_eq!(total, total_winnings(&mut hands, 1));
+    }
+
+    proptest! {
+        #[test]
+        fn joker_kind_matches_substitution(hand in hand()) {
+            let ruleset = Ruleset::part_two();
+            let category = ruleset.category_rank(ruleset.key(&hand).unwrap());
+            prop_assert_eq!(category, kind_by_substitution(&hand));
+        }
+
+        /// Keys compare like the category and then the cards one by one
+        #[test]
+        fn keys_order_like_cards(a in hand(), b in hand()) {
+            for ruleset in [Ruleset::part_one(), Ruleset::part_two()] {
+                let strength = |hand: &str| {
+                    let key = ruleset.key(hand).unwrap();
+                    let ranks = hand.chars().map(|card| ruleset.rank(card).unwrap()).collect::<Vec<_>>();
+                    (key, (ruleset.category_rank(key), ranks))
+                };
+                let ((key_a, a), (key_b, b)) = (strength(&a), strength(&b));
+                prop_assert_eq!(key_a.cmp(&key_b), a.cmp(&b));
+            }
+        }
+    }
+}
@@ -0,0 +1,399 @@
+//! The rules of a game of Camel Cards, from which cards, hand sizes and categories
+//! any hand is ranked.
+
+use anyhow::{bail, Context, Result};
+
+/// A category of hands like a full house, which needs groups of equal cards of atleast some sizes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Category {
+    pub name: String,
+    /// The sizes of the groups, largest first, a full house needs groups of 3 and 2 cards
+    pub groups: Vec<usize>,
+}
+
+impl Category {
+    pub fn new(name: &str, groups: &[usize]) -> Self {
+        let mut groups = groups.to_vec();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        Self {
+            name: name.to_string(),
+            groups,
+        }
+    }
+}
+
+/// The category of a hand, and what its wildcards stand for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Classification {
+    /// The index of the category in [`Ruleset::categories`]
+    pub category: usize,
+    /// Every card the wildcards of the hand were turned into, with how many of them
+    pub wildcards: Vec<(char, usize)>,
+}
+
+/// Which cards there are and how they rank, which of them are wild,
+/// how many cards make a hand and which categories of hands there are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ruleset {
+    pub name: String,
+    /// Every card, strongest first
+    cards: Vec<char>,
+    wildcards: Vec<char>,
+    hand_size: usize,
+    /// Strongest first
+    categories: Vec<Category>,
+}
+
+impl Ruleset {
+    /// A ruleset with the `cards` listed strongest first and the `categories` listed strongest first.
+    /// A hand falls in the strongest category it has the groups for.
+    pub fn new(
+        name: &str,
+        cards: &str,
+        wildcards: &str,
+        hand_size: usize,
+        categories: Vec<Category>,
+    ) -> Result<Self> {
+        let cards = cards.chars().collect::<Vec<_>>();
+        if cards.is_empty() {
+            bail!("A ruleset should have atleast one card");
+        }
+        if let Some(card) = cards
+            .iter()
+            .enumerate()
+            .find(|(i, card)| cards[..*i].contains(card))
+        {
+            bail!("Card {} should appear only once", card.1);
+        }
+        let wildcards = wildcards.chars().collect::<Vec<_>>();
+        if let Some(card) = wildcards.iter().find(|card| !cards.contains(card)) {
+            bail!("Wildcard {} should be one of the cards", card);
+        }
+        if hand_size == 0 {
+            bail!("A hand should have atleast one card");
+        }
+        if categories.is_empty() {
+            bail!("A ruleset should have atleast one category");
+        }
+        let ruleset = Self {
+            name: name.to_string(),
+            cards,
+            wildcards,
+            hand_size,
+            categories,
+        };
+        let key_bits = ruleset.category_bits() as usize + hand_size * ruleset.card_bits() as usize;
+        if key_bits > u64::BITS as usize {
+            bail!(
+                "Hands of {} cards out of {} cards in {} categories need {} bits to rank, atmost {} fit",
+                hand_size,
+                ruleset.cards.len(),
+                ruleset.categories.len(),
+                key_bits,
+                u64::BITS
+            );
+        }
+        for category in ruleset.categories.iter() {
+            if category.groups.contains(&0) || category.groups.iter().sum::<usize>() > hand_size {
+                bail!(
+                    "Category {} should have groups of atleast 1 card, and atmost {} cards in total",
+                    category.name,
+                    hand_size
+                );
+            }
+        }
+        Ok(ruleset)
+    }
+
+    /// Five, four and three of a kind, full house, two pair, one pair and high card
+    pub fn standard_categories() -> Vec<Category> {
+        vec![
+            Category::new("five of a kind", &[5]),
+            Category::new("four of a kind", &[4]),
+            Category::new("full house", &[3, 2]),
+            Category::new("three of a kind", &[3]),
+            Category::new("two pair", &[2, 2]),
+            Category::new("one pair", &[2]),
+            Category::new("high card", &[]),
+        ]
+    }
+
+    /// The rules of part one
+    pub fn part_one() -> Self {
+        Self::new(
+            "part one",
+            "AKQJT98765432",
+            "",
+            5,
+            Self::standard_categories(),
+        )
+        .unwrap()
+    }
+
+    /// The rules of part two, where jokers are wild and the weakest card
+    pub fn part_two() -> Self {
+        Self::new(
+            "part two",
+            "AKQT98765432J",
+            "J",
+            5,
+            Self::standard_categories(),
+        )
+        .unwrap()
+    }
+
+    /// Parse a ruleset file, with a `key = value` pair on every line:
+    /// `name`, `cards` strongest first, `wildcards` and `hand size`.
+    /// Every other key is a category with the sizes of its groups, like `full house = 3 2`,
+    /// strongest first. Without categories the standard ones are used.
+    /// Empty lines and lines starting with `#` are skipped.
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut name = "custom".to_string();
+        let mut cards = None;
+        let mut wildcards = String::new();
+        let mut hand_size = 5;
+        let mut categories = vec![];
+        for line in input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let (key, value) = line
+                .split_once('=')
+                .with_context(|| format!("Expected key = value, got: {}", line))?;
+            let (key, value) = (key.trim(), value.trim());
+            match key {
+                "name" => name = value.to_string(),
+                "cards" => cards = Some(value.to_string()),
+                "wildcards" => wildcards = value.to_string(),
+                "hand size" => {
+                    hand_size = value
+                        .parse()
+                        .with_context(|| format!("Expected a hand size, got: {}", value))?
+                }
+                _ => {
+                    let groups = value
+                        .split_ascii_whitespace()
+                        .map(|size| {
+                            size.parse()
+                                .with_context(|| format!("Expected a group size, got: {}", size))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    categories.push(Category::new(key, &groups));
+                }
+            }
+        }
+        if categories.is_empty() {
+            categories = Self::standard_categories();
+        }
+        let cards = cards.context("A ruleset should list its cards")?;
+        Self::new(&name, &cards, &wildcards, hand_size, categories)
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    /// Every category, strongest first
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// The rank of a card, 0 for the weakest card. None when the card is not part of the game.
+    pub fn rank(&self, card: char) -> Option<usize> {
+        let i = self.cards.iter().position(|c| *c == card)?;
+        Some(self.cards.len() - 1 - i)
+    }
+
+    /// The cards of a hand, which should have the right number of cards that are part of the game
+    pub fn check(&self, hand: &str) -> Result<Vec<char>> {
+        let cards = hand.chars().collect::<Vec<_>>();
+        if cards.len() != self.hand_size {
+            bail!("A hand should have {} cards, got: {}", self.hand_size, hand);
+        }
+        if let Some(card) = cards.iter().find(|card| self.rank(**card).is_none()) {
+            bail!("Invalid card: {}", card);
+        }
+        Ok(cards)
+    }
+
+    /// The strongest category of a hand, turning the wildcards into whatever cards help most.
+    ///
+    /// Groups of equal cards, largest first, are lined up with the groups of a category,
+    /// largest first, and the wildcards fill up what is missing.
+    pub fn classify(&self, hand: &str) -> Result<Classification> {
+        let cards = self.check(hand)?;
+        let wild = cards.iter().filter(|c| self.wildcards.contains(c)).count();
+        // (size, card) of every group of equal cards that are not wild, largest and strongest first
+        let mut groups: Vec<(usize, char)> = vec![];
+        for card in cards.iter().filter(|c| !self.wildcards.contains(c)) {
+            match groups.iter_mut().find(|(_, c)| c == card) {
+                Some((size, _)) => *size += 1,
+                None => groups.push((1, *card)),
+            }
+        }
+        groups.sort_unstable_by_key(|(size, card)| std::cmp::Reverse((*size, self.rank(*card))));
+
+        for (i, category) in self.categories.iter().enumerate() {
+            // what every group of the category misses, with the card it is made of
+            let missing =
+                category
+                    .groups
+                    .iter()
+                    .enumerate()
+                    .map(|(j, needed)| match groups.get(j) {
+                        Some((size, card)) => (needed.saturating_sub(*size), Some(*card)),
+                        None => (*needed, None),
+                    });
+            if missing.clone().map(|(missing, _)| missing).sum::<usize>() > wild {
+                continue;
+            }
+
+            // new groups are made of the strongest cards that are not in the hand
+            let mut unused = self
+                .cards
+                .iter()
+                .filter(|c| !self.wildcards.contains(c) && !cards.contains(c));
+            let mut wildcards: Vec<(char, usize)> = vec![];
+            for (count, card) in missing.filter(|(missing, _)| *missing > 0) {
+                let card = card
+                    .or_else(|| unused.next().copied())
+                    .unwrap_or(self.wildcards[0]);
+                wildcards.push((card, count));
+            }
+            // the wildcards that are left join the largest group
+            let left = wild - wildcards.iter().map(|(_, count)| count).sum::<usize>();
+            if left > 0 {
+                let card = groups
+                    .first()
+                    .map(|(_, card)| *card)
+                    .or_else(|| wildcards.first().map(|(card, _)| *card))
+                    .unwrap_or(self.cards[0]);
+                match wildcards.iter_mut().find(|(c, _)| *c == card) {
+                    Some((_, count)) => *count += left,
+                    None => wildcards.insert(0, (card, left)),
+                }
+            }
+            return Ok(Classification {
+                category: i,
+                wildcards,
+            });
+        }
+        bail!(
+            "Hand {} is in none of the categories of {}",
+            hand,
+            self.name
+        )
+    }
+
+    /// The bits of a key that hold the category
+    fn category_bits(&self) -> u32 {
+        usize::BITS - (self.categories.len() - 1).leading_zeros()
+    }
+
+    /// The bits of a key that hold the rank of a single card
+    fn card_bits(&self) -> u32 {
+        usize::BITS - (self.cards.len() - 1).leading_zeros()
+    }
+
+    /// How strong a hand is, first by category and then by its cards in order, packed into
+    /// a single integer: the rank of the category in the highest bits, followed by the rank of
+    /// every card. Stronger hands have greater keys.
+    pub fn key(&self, hand: &str) -> Result<u64> {
+        let classification = self.classify(hand)?;
+        let category = (self.categories.len() - 1 - classification.category) as u64;
+        let card_bits = self.card_bits();
+        // the ranks are checked by classify
+        Ok(hand.chars().fold(category, |key, card| {
+            key << card_bits | self.rank(card).unwrap() as u64
+        }))
+    }
+
+    /// The rank of the category of a hand from its key, 0 for the weakest category.
+    /// When the cards fill all 64 bits, there is a single category.
+    pub fn category_rank(&self, key: u64) -> usize {
+        key.checked_shr(self.hand_size as u32 * self.card_bits())
+            .unwrap_or(0) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jokers_and_deuces_wild() {
+        let ruleset = Ruleset::parse(
+            "# jokers and deuces wild
+name = wild
+cards = AKQT9876543J2
+wildcards = J2",
+        )
+        .unwrap();
+        let classify = |hand| {
+            let classification = ruleset.classify(hand).unwrap();
+            let category = &ruleset.categories()[classification.category].name;
+            (category.clone(), classification.wildcards)
+        };
+        assert_eq!(
+            classify("2J3A4"),
+            ("three of a kind".into(), vec![('A', 2)])
+        );
+        assert_eq!(classify("22JJ2"), ("five of a kind".into(), vec![('A', 5)]));
+        assert_eq!(classify("KK2QQ"), ("full house".into(), vec![('K', 1)]));
+        assert_eq!(classify("T9876"), ("high card".into(), vec![]));
+        assert!(ruleset.key("2AAAA").unwrap() < ruleset.key("JAAAA").unwrap());
+        assert!(ruleset.key("JAAAA").unwrap() > ruleset.key("3AAAA").unwrap());
+    }
+
+    #[test]
+    fn six_card_hands() {
+        let ruleset = Ruleset::parse(
+            "cards = AKQJT98765432
+hand size = 6
+two triples = 3 3
+full house = 3 2
+three pair = 2 2 2
+two pair = 2 2
+one pair = 2
+high card =",
+        )
+        .unwrap();
+        let category = |hand| {
+            ruleset.categories()[ruleset.classify(hand).unwrap().category]
+                .name
+                .clone()
+        };
+        assert_eq!(category("AAAKKK"), "two triples");
+        assert_eq!(category("AAAAKK"), "full house");
+        assert_eq!(category("AAKKQQ"), "three pair");
+        assert!(ruleset.classify("AAKKQ").is_err());
+        assert!(ruleset.classify("AAKKQX").is_err());
+    }
+
+    #[test]
+    fn invalid_rulesets() {
+        assert!(Ruleset::parse("cards = AKK").is_err());
+        assert!(Ruleset::parse("cards = AK\nwildcards = J").is_err());
+        assert!(Ruleset::parse("cards = AK\nhand size = 2\ntriple = 3").is_err());
+        assert!(Ruleset::parse("wildcards = J").is_err());
+        // 7 cards of 3 bits for 13 cards do not leave enough room for 7 categories in 64 bits
+        assert!(Ruleset::parse("cards = AKQJT98765432\nhand size = 20").is_err());
+        // without high card, a hand may fall in no category
+        let ruleset = Ruleset::parse("cards = AK\nhand size = 2\npair = 2").unwrap();
+        assert!(ruleset.classify("AK").is_err());
+    }
+
+    /// Hands of 16 cards out of 16 cards take 4 bits a card, which fills all 64 bits
+    /// and leaves none for the single category
+    #[test]
+    fn keys_that_fill_64_bits() {
+        let ruleset =
+            Ruleset::parse("cards = AKQJT98765432XYZ\nhand size = 16\nhigh card =").unwrap();
+        let strongest = ruleset.key("AAAAAAAAAAAAAAAA").unwrap();
+        let weakest = ruleset.key("ZZZZZZZZZZZZZZZZ").unwrap();
+        assert!(strongest > weakest);
+        assert_eq!(ruleset.category_rank(strongest), 0);
+        assert_eq!(ruleset.category_rank(weakest), 0);
+    }
+}
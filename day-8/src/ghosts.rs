@@ -0,0 +1,307 @@
+//! When walkers that follow the same instructions from different nodes all stand on an end node.
+
+use std::collections::HashMap;
+
+use num::{BigInt, Integer, One, Zero};
+
+use crate::Network;
+
+/// The steps at which a walk stands on an end node.
+///
+/// After `prefix` steps the walk is in a state, a node at an instruction, it returns to every
+/// `cycle` steps from then on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Walk {
+    pub start: String,
+    pub prefix: u64,
+    pub cycle: u64,
+    /// The steps before `prefix` that end on an end node, in order
+    pub prefix_hits: Vec<u64>,
+    /// The steps from `prefix` up to `prefix + cycle` that end on an end node, in order.
+    /// They repeat every `cycle` steps.
+    pub cycle_hits: Vec<u64>,
+}
+
+impl Walk {
+    /// A walk with its hits, which should be in order and lie before `prefix` or in the cycle
+    pub fn new(
+        start: String,
+        prefix: u64,
+        cycle: u64,
+        prefix_hits: Vec<u64>,
+        cycle_hits: Vec<u64>,
+    ) -> Walk {
+        debug_assert!(cycle > 0, "A walk repeats after at least one step");
+        debug_assert!(
+            prefix_hits.windows(2).all(|w| w[0] < w[1])
+                && prefix_hits.iter().all(|hit| *hit < prefix),
+            "Prefix hits should be in order and before the cycle: {prefix_hits:?}"
+        );
+        debug_assert!(
+            cycle_hits.windows(2).all(|w| w[0] < w[1])
+                && cycle_hits
+                    .iter()
+                    .all(|hit| (prefix..prefix + cycle).contains(hit)),
+            "Cycle hits should be in order and within {prefix}..{}: {cycle_hits:?}",
+            prefix + cycle
+        );
+        Walk {
+            start,
+            prefix,
+            cycle,
+            prefix_hits,
+            cycle_hits,
+        }
+    }
+
+    /// Whether the walk stands on an end node after `step` steps
+    pub fn is_hit(&self, step: u64) -> bool {
+        if step < self.prefix {
+            return self.prefix_hits.binary_search(&step).is_ok();
+        }
+        let step = self.prefix + (step - self.prefix) % self.cycle;
+        self.cycle_hits.binary_search(&step).is_ok()
+    }
+
+    /// The fewest steps to an end node, None when the walk never gets to one
+    pub fn first_hit(&self) -> Option<u64> {
+        self.prefix_hits
+            .first()
+            .or(self.cycle_hits.first())
+            .copied()
+    }
+
+    /// Whether the walk stands on an end node exactly every `cycle` steps and never otherwise,
+    /// which is what the LCM of the cycles takes for granted
+    pub fn hits_every_cycle(&self) -> bool {
+        self.prefix_hits.is_empty() && self.cycle_hits == [self.cycle]
+    }
+}
+
+impl Network<'_> {
+    /// Walk from `start` until the walk repeats itself, noting every step that ends on a node
+    /// for which `is_end` holds. None when the walk leaves the network or there are no instructions.
+    pub fn walk(&self, start: &str, is_end: impl Fn(&str) -> bool) -> Option<Walk> {
+        let instructions = self.instructions.as_bytes();
+        if instructions.is_empty() {
+            return None;
+        }
+        // the step every state was first seen at
+        let mut seen = HashMap::new();
+        let mut hits = vec![];
+        let mut node = start;
+        let mut step = 0u64;
+        let prefix = loop {
+            let instruction = (step % instructions.len() as u64) as usize;
+            if let Some(first) = seen.insert((node, instruction), step) {
+                break first;
+            }
+            if is_end(node) {
+                hits.push(step);
+            }
+            let (left, right) = *self.nodes.get(node)?;
+            node = match instructions[instruction] {
+                b'L' => left,
+                _ => right,
+            };
+            step += 1;
+        };
+
+        let cycle_hits = hits.split_off(hits.partition_point(|hit| *hit < prefix));
+        Some(Walk::new(
+            start.to_string(),
+            prefix,
+            step - prefix,
+            hits,
+            cycle_hits,
+        ))
+    }
+}
+
+/// How the walks were found to meet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    /// Before every walk was in its cycle
+    Prefix,
+    /// At the least common multiple of the cycles, every walk hits once at the end of its cycle
+    Lcm,
+    /// By the generalized chinese remainder theorem over every combination of hits in the cycles
+    Crt,
+}
+
+/// The fewest steps after which every walk stands on an end node, None when that never happens
+/// or there are no walks at all
+pub fn all_hit(walks: &[Walk]) -> Option<(BigInt, Method)> {
+    if walks.is_empty() {
+        return None;
+    }
+    // before every walk is in its cycle, try every hit of a single walk
+    let in_cycles = walks.iter().map(|walk| walk.prefix).max().unwrap_or(0);
+    if let Some(walk) = walks.first() {
+        let mut candidates = walk
+            .prefix_hits
+            .iter()
+            .copied()
+            .chain(
+                walk.cycle_hits
+                    .iter()
+                    .flat_map(|hit| (*hit..in_cycles).step_by(walk.cycle as usize)),
+            )
+            .filter(|step| *step < in_cycles)
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        if let Some(step) = candidates
+            .into_iter()
+            .find(|step| walks.iter().all(|walk| walk.is_hit(*step)))
+        {
+            return Some((BigInt::from(step), Method::Prefix));
+        }
+    }
+
+    if walks.iter().all(Walk::hits_every_cycle) {
+        let steps = walks
+            .iter()
+            .map(|walk| BigInt::from(walk.cycle))
+            .fold(BigInt::one(), |a, b| a.lcm(&b));
+        return Some((steps, Method::Lcm));
+    }
+
+    // every residue, modulo the lcm of the cycles so far, at which all walks so far are on an end node
+    let mut modulus = BigInt::one();
+    let mut residues = vec![BigInt::zero()];
+    for walk in walks {
+        let cycle = BigInt::from(walk.cycle);
+        let mut combined = vec![];
+        for residue in residues.iter() {
+            for hit in walk.cycle_hits.iter() {
+                combined.extend(crt(residue, &modulus, &BigInt::from(*hit), &cycle));
+            }
+        }
+        if combined.is_empty() {
+            return None;
+        }
+        combined.sort_unstable();
+        combined.dedup();
+        modulus = modulus.lcm(&cycle);
+        residues = combined;
+    }
+
+    // the first step with one of the residues once every walk is in its cycle
+    let in_cycles = BigInt::from(in_cycles);
+    let steps = residues
+        .iter()
+        .map(|residue| {
+            let behind = (&in_cycles - residue).mod_floor(&modulus);
+            if behind.is_zero() {
+                in_cycles.clone()
+            } else {
+                &in_cycles + &modulus - behind
+            }
+        })
+        .min()?;
+    Some((steps, Method::Crt))
+}
+
+/// The x with x = a mod m and x = b mod n, as a residue modulo lcm(m, n).
+/// None when there is no such x, because a and b differ by something gcd(m, n) does not divide.
+fn crt(a: &BigInt, m: &BigInt, b: &BigInt, n: &BigInt) -> Option<BigInt> {
+    let gcd = m.extended_gcd(n);
+    let difference = b - a;
+    if !(&difference % &gcd.gcd).is_zero() {
+        return None;
+    }
+    let lcm = m / &gcd.gcd * n;
+    // m * x = b - a modulo n, where x is the inverse of m / gcd modulo n / gcd
+    let x = (&difference / &gcd.gcd * &gcd.x).mod_floor(&(n / &gcd.gcd));
+    Some((a + m * x).mod_floor(&lcm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    fn walk(prefix: u64, cycle: u64, prefix_hits: &[u64], cycle_hits: &[u64]) -> Walk {
+        Walk::new(
+            String::new(),
+            prefix,
+            cycle,
+            prefix_hits.to_vec(),
+            cycle_hits.to_vec(),
+        )
+    }
+
+    /// Try every step until all walks hit
+    fn all_hit_by_brute_force(walks: &[Walk]) -> Option<u64> {
+        (0..10_000).find(|step| walks.iter().all(|walk| walk.is_hit(*step)))
+    }
+
+    #[test]
+    fn example() {
+        let network = parse_input(
+            "LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)",
+        )
+        .unwrap();
+        let walks = ["11A", "22A"]
+            .map(|start| network.walk(start, |node| node.ends_with('Z')).unwrap())
+            .to_vec();
+        assert_eq!(walks[1].cycle_hits, [3, 6]);
+        assert_eq!(all_hit(&walks), Some((BigInt::from(6), Method::Crt)));
+        assert_eq!(walks[0].first_hit(), Some(2));
+        assert_eq!(network.walk("33A", |node| node.ends_with('Z')), None);
+    }
+
+    #[test]
+    fn offsets_and_prefixes() {
+        // hits at 3, 7, 11, ... and at 5, 11, 17, ..., the lcm of 12 would be wrong
+        let walks = [walk(1, 4, &[], &[3]), walk(0, 6, &[], &[5])];
+        assert_eq!(all_hit(&walks), Some((BigInt::from(11), Method::Crt)));
+
+        let walks = [walk(5, 4, &[2], &[6]), walk(3, 2, &[2], &[3])];
+        assert_eq!(all_hit(&walks), Some((BigInt::from(2), Method::Prefix)));
+
+        let walks = [walk(1, 4, &[], &[4]), walk(1, 6, &[], &[6])];
+        assert_eq!(all_hit(&walks), Some((BigInt::from(12), Method::Lcm)));
+
+        // always even against always odd
+        let walks = [walk(0, 4, &[], &[0, 2]), walk(1, 2, &[], &[1])];
+        assert_eq!(all_hit(&walks), None);
+
+        // no ghosts to walk at all
+        assert_eq!(all_hit(&[]), None);
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let walks = [
+            walk(3, 5, &[1], &[4, 6]),
+            walk(7, 6, &[2, 4], &[8, 11]),
+            walk(0, 9, &[], &[2, 5]),
+        ];
+        for n in 1..=walks.len() {
+            let expected = all_hit_by_brute_force(&walks[..n]).map(BigInt::from);
+            assert_eq!(all_hit(&walks[..n]).map(|(steps, _)| steps), expected);
+        }
+
+        // every walk hits once at the end of its cycle, so the lcm applies
+        let walks = [
+            walk(2, 6, &[], &[6]),
+            walk(3, 4, &[], &[4]),
+            walk(1, 10, &[], &[10]),
+        ];
+        for n in 1..=walks.len() {
+            let expected =
+                all_hit_by_brute_force(&walks[..n]).map(|steps| (BigInt::from(steps), Method::Lcm));
+            assert_eq!(all_hit(&walks[..n]), expected);
+        }
+    }
+}
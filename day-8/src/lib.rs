@@ -0,0 +1,60 @@
+pub mod ghosts;
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub struct Network<'a> {
+    /// A sequence of 'L' and 'R' instructions
+    pub instructions: &'a str,
+    /// Maps every node to its (left, right) nodes
+    pub nodes: HashMap<&'a str, (&'a str, &'a str)>,
+}
+
+pub fn parse_input(input: &str) -> Result<Network<'_>> {
+    let re = Regex::new(r"^(.*) = \((.*), (.*)\)")?;
+    let mut nodes = HashMap::new();
+
+    for line in input.lines().skip(2) {
+        let (_m, [parent, left, right]) = re.captures(line).context("should match")?.extract();
+        nodes.insert(parent, (left, right));
+    }
+
+    // every node we can walk to should be part of the network
+    for (left, right) in nodes.values() {
+        for node in [left, right] {
+            if !nodes.contains_key(node) {
+                bail!("Node {} is not part of the network", node);
+            }
+        }
+    }
+
+    let instructions = input
+        .lines()
+        .next()
+        .context("Expected a line of instructions")?;
+    if instructions.is_empty() {
+        bail!("Expected atleast one instruction");
+    }
+    if let Some(ch) = instructions.chars().find(|ch| *ch != 'L' && *ch != 'R') {
+        bail!("Unexpected instruction: {}", ch);
+    }
+
+    Ok(Network {
+        instructions,
+        nodes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_empty_instructions() {
+        let error = parse_input("\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap_err();
+        assert_eq!(error.to_string(), "Expected atleast one instruction");
+    }
+}
@@ -1,83 +1,70 @@
-use std::{collections::HashMap, env};
+use std::{env, io};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use aoc_input_lib::get_puzzle_input;
 use clap::Parser;
-use regex::Regex;
-use num::integer::lcm;
-use num::BigInt;
-
+use day_8::ghosts::all_hit;
+use day_8::parse_input;
 
 /// Wait for it
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// The puzzle input
+    /// The puzzle input, or - to read it from stdin
     #[arg()]
     input: Option<String>,
 
     /// Advent of code session token
     #[arg(short, long)]
     session: Option<String>,
+
+    /// Show the cycle every ghost walks in
+    #[arg(short, long)]
+    cycles: bool,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let session = args.session.or_else(|| env::var("SESSION").ok());
-    let input = if let Some(s) = args.input {
-        s
-    } else {
-        get_puzzle_input(2023, 8, session).context("Could not retrieve puzzle input!")?
+    let input = match args.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        None => get_puzzle_input(2023, 8, session).context("Could not retrieve puzzle input!")?,
     };
 
-    let re = Regex::new(r"^(.*) = \((.*), (.*)\)")?;
-    let mut nodes_map = HashMap::new();
-
-    for line in input.lines().skip(2) {
-        let (_m, [parent, left, right]) = re.captures(&line).context("should match")?.extract();
-        nodes_map.insert(parent, (left, right));
-    }
-
-    let instructions = input.lines().next().unwrap();
-    let mut root = "AAA";
-
-    let mut steps = 0;
-    for ch in instructions.chars().cycle() {
-        if root == "ZZZ" {
-            break;
-        }
-        steps += 1;
-        match ch {
-            'L' => root = nodes_map.get(root).unwrap().0,
-            'R' => root = nodes_map.get(root).unwrap().1,
-            _ => panic!("unexpected input"),
-        }
+    let network = parse_input(&input)?;
+    if !network.nodes.contains_key("AAA") {
+        bail!("Expected the network to contain node AAA");
     }
+    let steps = network
+        .walk("AAA", |node| node == "ZZZ")
+        .and_then(|walk| walk.first_hit())
+        .context("There is no way from AAA to ZZZ")?;
     println!("Part one: {}", steps);
 
-    let root_nodes: Vec<_> = nodes_map
-        .clone()
-        .into_keys()
-        .filter(|v| v.chars().last().is_some_and(|ch| ch == 'A'))
-        .collect();
-    let mut steps_for_each_root = Vec::new();
-    for mut root in root_nodes {
-        let mut steps: u64 = 0;
-        for ch in instructions.chars().cycle() {
-            if root.as_bytes()[2] == b'Z' {
-                break;
-            }
-            steps += 1;
-            match ch {
-                'L' => root = nodes_map.get(root).unwrap().0,
-                'R' => root = nodes_map.get(root).unwrap().1,
-                _ => panic!("unexpected input"),
-            }
+    let mut root_nodes: Vec<_> = network.nodes.keys().filter(|v| v.ends_with('A')).collect();
+    root_nodes.sort_unstable();
+    if root_nodes.is_empty() {
+        bail!("Expected atleast one node ending in A for the ghosts to start from");
+    }
+    let walks: Vec<_> = root_nodes
+        .into_iter()
+        .map(|root| network.walk(root, |node| node.ends_with('Z')))
+        .collect::<Option<_>>()
+        .context("Expected every ghost to stay in the network")?;
+    if args.cycles {
+        for walk in walks.iter() {
+            println!(
+                "{}: a cycle of {} steps from step {}, on Z at steps {:?} and every cycle at {:?}",
+                walk.start, walk.cycle, walk.prefix, walk.prefix_hits, walk.cycle_hits
+            );
         }
-        steps_for_each_root.push(steps);
     }
-    dbg!(&steps_for_each_root);
-    let bigs = steps_for_each_root.into_iter().map(|n| BigInt::from(n)).reduce(|a,b| lcm(a, b)).unwrap();
-    println!("Part two: {}", bigs);
+    let (steps, method) =
+        all_hit(&walks).context("The ghosts are never all on a node ending in Z at once")?;
+    if args.cycles {
+        println!("Found by {:?}", method);
+    }
+    println!("Part two: {}", steps);
     Ok(())
 }
@@ -0,0 +1,18 @@
+use anyhow::{bail, Result};
+
+/// Parse the history of every value in the report
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i32>>> {
+    input
+        .lines()
+        .map(|line| {
+            let history = line
+                .split_ascii_whitespace()
+                .map(|word| Ok(word.parse()?))
+                .collect::<Result<Vec<_>>>()?;
+            if history.is_empty() {
+                bail!("A history should contain atleast one value");
+            }
+            Ok(history)
+        })
+        .collect()
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // the parser should return an error on invalid input, never panic
+    let _ = day_3::parse_input(input);
+});
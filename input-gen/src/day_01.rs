@@ -0,0 +1,32 @@
+use rand::Rng;
+
+/// Lines of calibration values
+pub const SIZE: usize = 1000;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Lines of letters, spelled out digits and digits.
+/// Spelled out digits may overlap, as in `twone`, and every line has atleast one digit.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut line = String::new();
+        for _ in 0..rng.gen_range(1..=12) {
+            match rng.gen_range(0..4) {
+                0 => line.push(char::from_digit(rng.gen_range(1..=9), 10).unwrap()),
+                1 => line.push_str(WORDS[rng.gen_range(0..WORDS.len())]),
+                _ => line.push(rng.gen_range('a'..='z')),
+            }
+        }
+        if !line.chars().any(|ch| ch.is_ascii_digit()) {
+            let digit = char::from_digit(rng.gen_range(1..=9), 10).unwrap();
+            let at = rng.gen_range(0..=line.len());
+            line.insert(at, digit);
+        }
+        input.push_str(&line);
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,24 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// Games
+pub const SIZE: usize = 100;
+
+/// Games of up to 6 sets, every set reveals 1 to 20 cubes of a few colors.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for id in 1..=size {
+        let sets: Vec<String> = (0..rng.gen_range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                colors.shuffle(rng);
+                colors[..rng.gen_range(1..=3)]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect();
+        input.push_str(&format!("Game {}: {}\n", id, sets.join("; ")));
+    }
+    input
+}
@@ -0,0 +1,35 @@
+use rand::Rng;
+
+/// Rows and columns of the engine schematic
+pub const SIZE: usize = 140;
+
+const SYMBOLS: &[u8] = b"*#+$/=%@&-";
+
+/// A square schematic of numbers of 1 to 3 digits, symbols and periods.
+/// A number never ends a row, so it can't run into a number at the start of the next row.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut row = vec![b'.'; size];
+        let mut x = 0;
+        while x + 1 < size {
+            let len = rng.gen_range(1..=3);
+            if rng.gen_ratio(1, 6) && x + len < size {
+                row[x] = b'0' + rng.gen_range(1..=9);
+                for cell in &mut row[x + 1..x + len] {
+                    *cell = b'0' + rng.gen_range(0..=9);
+                }
+                // a number is always followed by a period or a symbol
+                x += len + 1;
+            } else {
+                if rng.gen_ratio(1, 12) {
+                    row[x] = SYMBOLS[rng.gen_range(0..SYMBOLS.len())];
+                }
+                x += 1;
+            }
+        }
+        input.push_str(std::str::from_utf8(&row).unwrap());
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,46 @@
+use rand::{seq::index, seq::SliceRandom, Rng};
+
+/// Scratchcards
+pub const SIZE: usize = 200;
+
+/// Cards of 10 winning numbers and 25 numbers you have.
+///
+/// Most cards match nothing, so the number of copies stays small however many cards there are,
+/// and matches never win copies of cards past the end of the table.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for number in 1..=size {
+        let matches = if rng.gen_ratio(1, 10) {
+            rng.gen_range(0..=10).min(size - number)
+        } else {
+            0
+        };
+        // 35 distinct numbers: the winning numbers, then the numbers that don't win
+        let distinct: Vec<usize> = index::sample(rng, 99, 35)
+            .into_iter()
+            .map(|n| n + 1)
+            .collect();
+        let winning = &distinct[..10];
+        let mut numbers: Vec<usize> = winning[..matches]
+            .iter()
+            .chain(&distinct[10..35 - matches])
+            .copied()
+            .collect();
+        numbers.shuffle(rng);
+
+        let format = |numbers: &[usize]| {
+            numbers
+                .iter()
+                .map(|n| format!("{:>2}", n))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        input.push_str(&format!(
+            "Card {:>3}: {} | {}\n",
+            number,
+            format(winning),
+            format(&numbers)
+        ));
+    }
+    input
+}
@@ -0,0 +1,57 @@
+use rand::{seq::index, seq::SliceRandom, Rng};
+
+/// Entries of every map
+pub const SIZE: usize = 30;
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// 10 ranges of seeds, and maps of `size` entries.
+///
+/// Every map cuts 0..2^32 into `size` ranges and shuffles them around,
+/// so like the official input, the entries of a map never overlap.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    const END: u64 = 1 << 32;
+
+    let mut seeds = vec![];
+    for _ in 0..10 {
+        let len = rng.gen_range(1..=1 << 28);
+        seeds.push(rng.gen_range(0..=END - len).to_string());
+        seeds.push(len.to_string());
+    }
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    for name in MAPS {
+        let mut cuts: Vec<u64> = index::sample(rng, END as usize - 1, size - 1)
+            .into_iter()
+            .map(|cut| cut as u64 + 1)
+            .collect();
+        cuts.push(0);
+        cuts.push(END);
+        cuts.sort_unstable();
+        let mut sources: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+
+        // lay the ranges out again in a random order to find their destinations
+        sources.shuffle(rng);
+        let mut dest = 0;
+        let mut entries = vec![];
+        for (source, len) in sources {
+            entries.push((dest, source, len));
+            dest += len;
+        }
+        entries.shuffle(rng);
+
+        input.push_str(&format!("\n{} map:\n", name));
+        for (dest, source, len) in entries {
+            input.push_str(&format!("{} {} {}\n", dest, source, len));
+        }
+    }
+    input
+}
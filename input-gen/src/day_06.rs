@@ -0,0 +1,20 @@
+use rand::Rng;
+
+/// Races
+pub const SIZE: usize = 4;
+
+/// Races of 7 to 99 milliseconds, every record can be beaten.
+///
+/// Part two reads all races as a single one, which no longer fits in a u64 beyond 4 races.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut times = String::new();
+    let mut records = String::new();
+    for _ in 0..size {
+        let time: u64 = rng.gen_range(7..=99);
+        // holding the button for less than half the race never gives the best distance
+        let hold = rng.gen_range(1..time / 2);
+        times.push_str(&format!(" {:>4}", time));
+        records.push_str(&format!(" {:>4}", hold * (time - hold)));
+    }
+    format!("Time:    {}\nDistance:{}\n", times, records)
+}
@@ -0,0 +1,18 @@
+use rand::Rng;
+
+/// Hands
+pub const SIZE: usize = 1000;
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+/// Hands of 5 random cards, with a bid of 1 to 1000.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let hand: String = (0..5)
+            .map(|_| CARDS[rng.gen_range(0..CARDS.len())] as char)
+            .collect();
+        input.push_str(&format!("{} {}\n", hand, rng.gen_range(1..=1000)));
+    }
+    input
+}
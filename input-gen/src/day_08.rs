@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Instructions
+pub const SIZE: usize = 280;
+
+/// Distinct primes for the cycle of every ghost, in units of the instructions
+const PRIMES: [usize; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+/// `size` instructions, and a network with 6 ghosts walking from a node ending in A to a node ending in Z.
+///
+/// Like the official input, every ghost walks a cycle back to its Z node
+/// that is as long as the walk from its A node, a prime multiple of the number of instructions.
+/// The first ghost walks from AAA to ZZZ.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let instructions: Vec<char> = (0..size)
+        .map(|_| if rng.gen() { 'L' } else { 'R' })
+        .collect();
+    let primes: Vec<usize> = PRIMES.choose_multiple(rng, 6).copied().collect();
+    let total: usize = primes.iter().map(|p| p * size).sum();
+
+    let mut len = 3;
+    while 26 * 24usize.pow(len - 2) < total {
+        len += 1;
+    }
+    let mut names = Names {
+        len,
+        used: HashSet::new(),
+    };
+
+    // every ghost walks from its A node along a path of cycle - 1 nodes to its Z node,
+    // and Z leads to the same node as A
+    let mut walks = vec![];
+    for (ghost, p) in primes.iter().enumerate() {
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (names.next('A', rng), names.next('Z', rng))
+        };
+        let path: Vec<String> = (1..p * size)
+            .map(|_| {
+                let last = rng.gen_range('B'..='Y');
+                names.next(last, rng)
+            })
+            .collect();
+        walks.push((start, path, end));
+    }
+    let nodes: Vec<&str> = walks
+        .iter()
+        .flat_map(|(start, path, end)| path.iter().chain([start, end]))
+        .map(String::as_str)
+        .collect();
+
+    // the i-th step is always taken with instruction i % size,
+    // the other direction leads anywhere in the network
+    let mut lines = vec![];
+    let mut step = |from: &str, to: &str, i: usize, rng: &mut dyn rand::RngCore| {
+        let other = nodes.choose(rng).unwrap();
+        let (left, right) = if instructions[i % size] == 'L' {
+            (to, *other)
+        } else {
+            (*other, to)
+        };
+        lines.push(format!("{} = ({}, {})", from, left, right));
+    };
+    for (start, path, end) in walks.iter() {
+        step(start, &path[0], 0, rng);
+        step(end, &path[0], 0, rng);
+        for (i, from) in path.iter().enumerate() {
+            let to = path.get(i + 1).unwrap_or(end);
+            step(from, to, i + 1, rng);
+        }
+    }
+    lines.shuffle(rng);
+
+    format!(
+        "{}\n\n{}\n",
+        instructions.into_iter().collect::<String>(),
+        lines.join("\n")
+    )
+}
+
+/// Unique node names of the same length.
+/// All but the first and last letter are out of B..=Y, so they never clash with AAA and ZZZ.
+struct Names {
+    len: u32,
+    used: HashSet<String>,
+}
+
+impl Names {
+    fn next(&mut self, last: char, rng: &mut impl Rng) -> String {
+        loop {
+            let mut name = String::from(rng.gen_range('A'..='Z'));
+            for _ in 2..self.len {
+                name.push(rng.gen_range('B'..='Y'));
+            }
+            name.push(last);
+            if self.used.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+}
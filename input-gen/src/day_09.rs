@@ -0,0 +1,30 @@
+use rand::Rng;
+
+/// Histories
+pub const SIZE: usize = 200;
+
+/// Histories of 21 values of a polynomial of degree up to 12,
+/// so extrapolating forwards and backwards both fit in an i32.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        // coefficients in the basis of binomials, x choose k
+        let coefficients: Vec<i64> = (0..=rng.gen_range(0..=12))
+            .map(|_| rng.gen_range(-10..=10))
+            .collect();
+        let history: Vec<String> = (0..21)
+            .map(|x| {
+                let mut binomial = 1;
+                let mut value = 0;
+                for (k, c) in coefficients.iter().enumerate() {
+                    value += c * binomial;
+                    binomial = binomial * (x - k as i64) / (k as i64 + 1);
+                }
+                value.to_string()
+            })
+            .collect();
+        input.push_str(&history.join(" "));
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,80 @@
+use rand::Rng;
+
+use crate::outline::Outline;
+
+/// Rows and columns of the field of pipes
+pub const SIZE: usize = 140;
+
+/// The smallest field that fits a loop
+pub const MIN_SIZE: usize = 3;
+
+const PIPES: [char; 7] = ['|', '-', 'L', 'J', '7', 'F', '.'];
+
+/// A square field with a single loop of pipes through S, every other tile is a random pipe or ground.
+///
+/// The loop winds around a random tree, which encloses tiles where its branches are wide enough.
+/// S sits on a vertical pipe with nothing connecting to it from the left,
+/// as the day-10 solver counts S as a vertical pipe when it looks for enclosed tiles.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let side = (size + 1) / 4;
+    let outline = Outline::random(side, (side * side * 3 / 5).max(1), rng);
+    let offset = (
+        rng.gen_range(0..=size - outline.size),
+        rng.gen_range(0..=size - outline.size),
+    );
+
+    let mut field: Vec<Vec<char>> = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| PIPES[rng.gen_range(0..PIPES.len())])
+                .collect()
+        })
+        .collect();
+    let mut on_loop = vec![vec![false; size]; size];
+    let points = &outline.points;
+    for (i, (x, y)) in points.iter().enumerate() {
+        let previous = points[(i + points.len() - 1) % points.len()];
+        let next = points[(i + 1) % points.len()];
+        let (x, y) = (x + offset.0, y + offset.1);
+        let (previous, next) = (
+            (previous.0 + offset.0, previous.1 + offset.1),
+            (next.0 + offset.0, next.1 + offset.1),
+        );
+
+        let connects = |dx: isize, dy: isize| {
+            [previous, next].contains(&(x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)))
+        };
+        field[y][x] = match (
+            connects(0, -1),
+            connects(0, 1),
+            connects(-1, 0),
+            connects(1, 0),
+        ) {
+            (true, true, _, _) => '|',
+            (_, _, true, true) => '-',
+            (true, _, _, true) => 'L',
+            (true, _, true, _) => 'J',
+            (_, true, true, _) => '7',
+            _ => 'F',
+        };
+        on_loop[y][x] = true;
+    }
+
+    let candidates: Vec<(usize, usize)> = points
+        .iter()
+        .map(|(x, y)| (x + offset.0, y + offset.1))
+        .filter(|(x, y)| field[*y][*x] == '|' && !(*x > 0 && on_loop[*y][x - 1]))
+        .collect();
+    let (x, y) = candidates[rng.gen_range(0..candidates.len())];
+    field[y][x] = 'S';
+    if x > 0 {
+        field[y][x - 1] = '.';
+    }
+
+    let mut input = String::new();
+    for row in field {
+        input.extend(row);
+        input.push('\n');
+    }
+    input
+}
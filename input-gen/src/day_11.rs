@@ -0,0 +1,16 @@
+use rand::Rng;
+
+/// Rows and columns of the image
+pub const SIZE: usize = 140;
+
+/// A square image where about one in 45 pixels is a galaxy.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(if rng.gen_ratio(1, 45) { '#' } else { '.' });
+        }
+        input.push('\n');
+    }
+    input
+}
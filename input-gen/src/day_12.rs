@@ -0,0 +1,33 @@
+use rand::Rng;
+
+/// Rows of springs
+pub const SIZE: usize = 1000;
+
+/// Rows of up to 6 groups of damaged springs, where about half of the springs are unknown.
+/// Every row has atleast one arrangement.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let groups: Vec<usize> = (0..rng.gen_range(1..=6))
+            .map(|_| rng.gen_range(1..=5))
+            .collect();
+
+        // lay out an arrangement, then forget about some springs
+        let mut springs = ".".repeat(rng.gen_range(0..=3));
+        for (i, group) in groups.iter().enumerate() {
+            if i > 0 {
+                springs.push_str(&".".repeat(rng.gen_range(1..=3)));
+            }
+            springs.push_str(&"#".repeat(*group));
+        }
+        springs.push_str(&".".repeat(rng.gen_range(0..=3)));
+        let springs: String = springs
+            .chars()
+            .map(|ch| if rng.gen() { '?' } else { ch })
+            .collect();
+
+        let groups: Vec<String> = groups.iter().map(usize::to_string).collect();
+        input.push_str(&format!("{} {}\n", springs, groups.join(",")));
+    }
+    input
+}
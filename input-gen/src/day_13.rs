@@ -0,0 +1,93 @@
+use rand::Rng;
+
+/// Patterns
+pub const SIZE: usize = 100;
+
+/// Patterns of 5 to 17 rows and columns.
+///
+/// Every pattern has exactly one line of reflection,
+/// and exactly one other line of reflection once its smudge is fixed.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let patterns: Vec<String> = (0..size)
+        .map(|_| loop {
+            if let Some(pattern) = try_pattern(rng) {
+                return pattern
+                    .iter()
+                    .map(|row| row.iter().collect::<String>())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+            }
+        })
+        .collect();
+    format!("{}\n", patterns.join("\n\n"))
+}
+
+/// Mirror a random pattern in both a horizontal and a vertical line,
+/// then smudge it so only the horizontal line is left.
+/// Gives up when the pattern happens to have other lines of reflection.
+fn try_pattern(rng: &mut impl Rng) -> Option<Vec<Vec<char>>> {
+    let (rows, cols) = (rng.gen_range(5..=17), rng.gen_range(5..=17));
+    let mut pattern: Vec<Vec<char>> = (0..rows)
+        .map(|_| {
+            (0..cols)
+                .map(|_| if rng.gen() { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    // the horizontal line should leave some rows without a reflection to smudge
+    let horizontal = rng.gen_range(1..rows);
+    if 2 * horizontal == rows {
+        return None;
+    }
+    let vertical = rng.gen_range(1..cols);
+    for row in pattern.iter_mut() {
+        for i in 0..vertical.min(cols - vertical) {
+            row[vertical + i] = row[vertical - 1 - i];
+        }
+    }
+    for i in 0..horizontal.min(rows - horizontal) {
+        pattern[horizontal + i] = pattern[horizontal - 1 - i].clone();
+    }
+
+    let row = if 2 * horizontal > rows {
+        rng.gen_range(0..2 * horizontal - rows)
+    } else {
+        rng.gen_range(2 * horizontal..rows)
+    };
+    let col = vertical + rng.gen_range(0..vertical.min(cols - vertical));
+    pattern[row][col] = if pattern[row][col] == '#' { '.' } else { '#' };
+
+    let transposed: Vec<Vec<char>> = (0..cols)
+        .map(|col| pattern.iter().map(|row| row[col]).collect())
+        .collect();
+    let differences: Vec<usize> = differences(&pattern)
+        .into_iter()
+        .chain(differences(&transposed))
+        .collect();
+    let count = |n| differences.iter().filter(|d| **d == n).count();
+    if count(0) != 1 || count(1) != 1 {
+        return None;
+    }
+
+    // flip the pattern around half of the time, for vertical reflections in part one
+    if rng.gen() {
+        Some(transposed)
+    } else {
+        Some(pattern)
+    }
+}
+
+/// For every horizontal line, the number of cells that differ from their reflection
+fn differences(pattern: &[Vec<char>]) -> Vec<usize> {
+    (1..pattern.len())
+        .map(|line| {
+            let above = pattern[..line].iter().rev();
+            let below = pattern[line..].iter();
+            above
+                .zip(below)
+                .map(|(a, b)| a.iter().zip(b).filter(|(a, b)| a != b).count())
+                .sum()
+        })
+        .collect()
+}
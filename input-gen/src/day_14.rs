@@ -0,0 +1,20 @@
+use rand::Rng;
+
+/// Rows and columns of the platform
+pub const SIZE: usize = 100;
+
+/// A square platform with rounded rocks on a fifth and cube rocks on a tenth of the positions.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(match rng.gen_range(0..10) {
+                0 | 1 => 'O',
+                2 => '#',
+                _ => '.',
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,26 @@
+use rand::{seq::SliceRandom, Rng};
+
+/// Steps
+pub const SIZE: usize = 4000;
+
+/// Steps on `size / 8` labels of 2 to 6 letters, a third of them remove a lens.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let labels: Vec<String> = (0..size / 8 + 1)
+        .map(|_| {
+            (0..rng.gen_range(2..=6))
+                .map(|_| rng.gen_range('a'..='z'))
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..size)
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_ratio(1, 3) {
+                format!("{}-", label)
+            } else {
+                format!("{}={}", label, rng.gen_range(1..=9))
+            }
+        })
+        .collect();
+    format!("{}\n", steps.join(","))
+}
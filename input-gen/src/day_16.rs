@@ -0,0 +1,22 @@
+use rand::Rng;
+
+/// Rows and columns of the contraption
+pub const SIZE: usize = 110;
+
+const TILES: [char; 4] = ['/', '\\', '|', '-'];
+
+/// A square contraption where about one in ten tiles is a mirror or a splitter.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(if rng.gen_ratio(1, 10) {
+                TILES[rng.gen_range(0..TILES.len())]
+            } else {
+                '.'
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,16 @@
+use rand::Rng;
+
+/// Rows and columns of the city
+pub const SIZE: usize = 141;
+
+/// A square city of heat losses 1 to 9.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            input.push(char::from_digit(rng.gen_range(1..=9), 10).unwrap());
+        }
+        input.push('\n');
+    }
+    input
+}
@@ -0,0 +1,68 @@
+use std::cmp::Ordering;
+
+use rand::Rng;
+
+use crate::outline::Outline;
+
+/// Branches of the lagoon
+pub const SIZE: usize = 250;
+
+/// A dig plan around a lagoon shaped like a random tree of `size` branches.
+///
+/// Both parts dig the same shape, stretched differently, so neither dig plan ever crosses itself.
+/// Part one digs 1 to 5 meters per unit, part two stretches the shape to fit 5 hex digits.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut side = 1;
+    while side * side < 2 * size {
+        side += 1;
+    }
+    let outline = Outline::random(side, size, rng);
+
+    // stretch every column and row of the grid by a random amount
+    let max = 0xfffff / outline.size as u64;
+    let mut stretch = |max: u64| {
+        let mut position = 0;
+        let mut positions = vec![];
+        for _ in 0..outline.size {
+            positions.push(position);
+            position += rng.gen_range(1..=max);
+        }
+        positions
+    };
+    let (xs, ys) = (stretch(5), stretch(5));
+    let (xs_hex, ys_hex) = (stretch(max), stretch(max));
+
+    // merge the steps of the outline into straight lines,
+    // the outline starts walking right from a corner
+    let points = &outline.points;
+    let n = points.len();
+    let direction = |i: usize| {
+        let ((x, y), (next_x, next_y)) = (points[i], points[(i + 1) % n]);
+        match (next_x.cmp(&x), next_y.cmp(&y)) {
+            (Ordering::Greater, _) => ('R', 0),
+            (_, Ordering::Greater) => ('D', 1),
+            (Ordering::Less, _) => ('L', 2),
+            _ => ('U', 3),
+        }
+    };
+    let mut input = String::new();
+    let mut start = 0;
+    for i in 0..n {
+        if i + 1 < n && direction(i + 1) == direction(i) {
+            continue;
+        }
+        let (from, to) = (points[start], points[(i + 1) % n]);
+        let distance =
+            |xs: &[u64], ys: &[u64]| xs[from.0].abs_diff(xs[to.0]) + ys[from.1].abs_diff(ys[to.1]);
+        let (letter, digit) = direction(i);
+        input.push_str(&format!(
+            "{} {} (#{:05x}{})\n",
+            letter,
+            distance(&xs, &ys),
+            distance(&xs_hex, &ys_hex),
+            digit
+        ));
+        start = i + 1;
+    }
+    input
+}
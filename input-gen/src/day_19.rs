@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Workflows
+pub const SIZE: usize = 550;
+
+const CATEGORIES: [char; 4] = ['x', 'm', 'a', 's'];
+
+/// `size` workflows and as many parts.
+///
+/// The workflows form a tree rooted at `in`, so every workflow is reachable and no part is sent around in circles.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    // unique names of lowercase letters, never "in"
+    let mut len = 2;
+    while 26usize.pow(len) < 4 * size {
+        len += 1;
+    }
+    let mut used = HashSet::from(["in".to_string()]);
+    let mut names = vec!["in".to_string()];
+    while names.len() < size {
+        let name: String = (0..len).map(|_| rng.gen_range('a'..='z')).collect();
+        if used.insert(name.clone()) {
+            names.push(name);
+        }
+    }
+
+    // every workflow but in is sent parts from a single workflow before it
+    let mut destinations: Vec<Vec<&str>> = vec![vec![]; size];
+    for (i, name) in names.iter().enumerate().skip(1) {
+        destinations[rng.gen_range(0..i)].push(name);
+    }
+
+    let mut input = String::new();
+    for (name, mut destinations) in names.iter().zip(destinations) {
+        let rules = rng.gen_range(2..=4);
+        while destinations.len() < rules {
+            destinations.push(if rng.gen() { "A" } else { "R" });
+        }
+        destinations.shuffle(rng);
+
+        let last = destinations.pop().unwrap();
+        let mut rules: Vec<String> = destinations
+            .into_iter()
+            .map(|destination| {
+                let category = CATEGORIES.choose(rng).unwrap();
+                let op = if rng.gen() { '<' } else { '>' };
+                format!(
+                    "{}{}{}:{}",
+                    category,
+                    op,
+                    rng.gen_range(1..=4000),
+                    destination
+                )
+            })
+            .collect();
+        rules.push(last.to_string());
+        input.push_str(&format!("{}{{{}}}\n", name, rules.join(",")));
+    }
+
+    input.push('\n');
+    for _ in 0..size {
+        let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+        input.push_str(&format!("{{x={},m={},a={},s={}}}\n", x, m, a, s));
+    }
+    input
+}
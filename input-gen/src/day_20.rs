@@ -0,0 +1,61 @@
+use std::collections::HashSet;
+
+use rand::{seq::SliceRandom, Rng};
+
+/// Flip-flops of every counter
+pub const SIZE: usize = 12;
+
+/// The day-20 solver handles atmost 63 modules, 4 counters of 13 flip-flops take 62
+/// with their conjunctions, the inverters, the output and the broadcaster
+pub const MAX_SIZE: usize = 13;
+
+/// The conjunctions the day-20 solver watches, one for every counter
+const INVERTERS: [&str; 4] = ["lh", "fk", "ff", "mm"];
+
+/// A network of 4 counters of `size` flip-flops, shaped like the official input.
+///
+/// Every counter counts button presses up to a random odd number with its highest bit set.
+/// The flip-flops that are on at that number feed a conjunction,
+/// which resets the counter and sends a low pulse through an inverter to the conjunction in front of rx.
+pub fn generate(size: usize, rng: &mut impl Rng) -> String {
+    let mut used: HashSet<String> = INVERTERS.iter().map(|s| s.to_string()).collect();
+    used.insert("rx".into());
+    let mut name = |rng: &mut dyn rand::RngCore| loop {
+        let name: String = (0..2).map(|_| rng.gen_range('a'..='z')).collect();
+        if used.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let output = name(rng);
+    let mut lines = vec![format!("&{} -> rx", output)];
+    let mut counters = vec![];
+    for inverter in INVERTERS {
+        let count = rng.gen_range(1 << (size - 1)..1 << size) | 1;
+        let flipflops: Vec<String> = (0..size).map(|_| name(rng)).collect();
+        let hub = name(rng);
+
+        let mut hub_destinations = vec![inverter.to_string()];
+        for (bit, flipflop) in flipflops.iter().enumerate() {
+            let mut destinations = vec![];
+            if let Some(next) = flipflops.get(bit + 1) {
+                destinations.push(next.clone());
+            }
+            if count & (1 << bit) != 0 {
+                destinations.push(hub.clone());
+            }
+            if bit == 0 || count & (1 << bit) == 0 {
+                hub_destinations.push(flipflop.clone());
+            }
+            destinations.shuffle(rng);
+            lines.push(format!("%{} -> {}", flipflop, destinations.join(", ")));
+        }
+        hub_destinations.shuffle(rng);
+        lines.push(format!("&{} -> {}", hub, hub_destinations.join(", ")));
+        lines.push(format!("&{} -> {}", inverter, output));
+        counters.push(flipflops[0].clone());
+    }
+    lines.push(format!("broadcaster -> {}", counters.join(", ")));
+    lines.shuffle(rng);
+    format!("{}\n", lines.join("\n"))
+}
@@ -0,0 +1,163 @@
+//! Generate synthetic puzzle inputs of any size, to stress test and benchmark the solvers.
+//!
+//! Every solver reads its input from stdin when passed `-`:
+//!
+//! ```sh
+//! cargo run --release -p input-gen -- 16 --size 1100 --seed 7 | cargo run --release -p day-16 -- -
+//! ```
+//!
+//! Day 20 is the exception, its solver holds atmost 63 modules so its inputs can not grow
+//! much beyond the official size.
+mod day_01;
+mod day_02;
+mod day_03;
+mod day_04;
+mod day_05;
+mod day_06;
+mod day_07;
+mod day_08;
+mod day_09;
+mod day_10;
+mod day_11;
+mod day_12;
+mod day_13;
+mod day_14;
+mod day_15;
+mod day_16;
+mod day_17;
+mod day_18;
+mod day_19;
+mod day_20;
+mod outline;
+
+use anyhow::{bail, Result};
+use clap::Parser;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Synthetic puzzle inputs
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The day to generate a puzzle input for
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=20))]
+    day: u32,
+
+    /// How large the input should be, see the generator of each day for what it scales.
+    /// Defaults to the size of an official puzzle input.
+    /// Day 20 is excluded from scaling: its solver holds atmost 63 modules,
+    /// so the size is atmost 13 flip-flops per counter against the official 12
+    #[arg(short, long)]
+    size: Option<usize>,
+
+    /// Seed for the random number generator, the same seed and size give the same input
+    #[arg(long, default_value_t = 2023)]
+    seed: u64,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    print!("{}", generate(args.day, args.size, &mut rng)?);
+    Ok(())
+}
+
+fn generate(day: u32, size: Option<usize>, rng: &mut StdRng) -> Result<String> {
+    let size = size.unwrap_or(match day {
+        1 => day_01::SIZE,
+        2 => day_02::SIZE,
+        3 => day_03::SIZE,
+        4 => day_04::SIZE,
+        5 => day_05::SIZE,
+        6 => day_06::SIZE,
+        7 => day_07::SIZE,
+        8 => day_08::SIZE,
+        9 => day_09::SIZE,
+        10 => day_10::SIZE,
+        11 => day_11::SIZE,
+        12 => day_12::SIZE,
+        13 => day_13::SIZE,
+        14 => day_14::SIZE,
+        15 => day_15::SIZE,
+        16 => day_16::SIZE,
+        17 => day_17::SIZE,
+        18 => day_18::SIZE,
+        19 => day_19::SIZE,
+        _ => day_20::SIZE,
+    });
+    if size == 0 {
+        bail!("Expected a size of atleast 1");
+    }
+    if day == 10 && size < day_10::MIN_SIZE {
+        bail!("Expected a size of atleast {} for day 10", day_10::MIN_SIZE);
+    }
+    if day == 20 && size > day_20::MAX_SIZE {
+        bail!(
+            "Expected a size of atmost {} for day 20, its solver holds atmost 63 modules",
+            day_20::MAX_SIZE
+        );
+    }
+
+    Ok(match day {
+        1 => day_01::generate(size, rng),
+        2 => day_02::generate(size, rng),
+        3 => day_03::generate(size, rng),
+        4 => day_04::generate(size, rng),
+        5 => day_05::generate(size, rng),
+        6 => day_06::generate(size, rng),
+        7 => day_07::generate(size, rng),
+        8 => day_08::generate(size, rng),
+        9 => day_09::generate(size, rng),
+        10 => day_10::generate(size, rng),
+        11 => day_11::generate(size, rng),
+        12 => day_12::generate(size, rng),
+        13 => day_13::generate(size, rng),
+        14 => day_14::generate(size, rng),
+        15 => day_15::generate(size, rng),
+        16 => day_16::generate(size, rng),
+        17 => day_17::generate(size, rng),
+        18 => day_18::generate(size, rng),
+        19 => day_19::generate(size, rng),
+        _ => day_20::generate(size, rng),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::generate;
+
+    /// Every generated input, small or official sized, should be accepted by the parser of its day
+    #[test]
+    fn generated_inputs_parse() {
+        for size in [None, Some(1), Some(3), Some(13)] {
+            let input = |day| generate(day, size, &mut StdRng::seed_from_u64(day as u64)).unwrap();
+
+            day_1::parse_input(&input(1)).unwrap();
+            day_2::parse_input(&input(2)).unwrap();
+            day_3::parse_input(&input(3)).unwrap();
+            day_4::parse_input(&input(4)).unwrap();
+            day_5::parse_input(&input(5)).unwrap();
+            day_6::parse_input(&input(6)).unwrap();
+            day_7::parse_input(&input(7)).unwrap();
+            day_8::parse_input(&input(8)).unwrap();
+            day_9::parse_input(&input(9)).unwrap();
+            if size.is_none_or(|size| size >= super::day_10::MIN_SIZE) {
+                day_10::Grid::parse_input(&input(10)).unwrap();
+            }
+            day_11::parse_input(&input(11)).unwrap();
+            day_12::parse_input(&input(12)).unwrap();
+            day_13::parse_input(&input(13)).unwrap();
+            day_14::Platform::parse_platform(&input(14)).unwrap();
+            day_15::parse_input(&input(15)).unwrap();
+            day_16::Grid::parse_grid(&input(16)).unwrap();
+            day_17::Grid::parse_grid(&input(17)).unwrap();
+            day_18::parse_input(&input(18)).unwrap();
+            day_19::parse_input(&input(19)).unwrap();
+            day_20::State::from_input(&input(20)).unwrap();
+
+            // the same seed gives the same input
+            assert_eq!(input(14), input(14));
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use rand::Rng;
+
+/// A closed loop on a grid of points that never touches itself
+pub struct Outline {
+    /// Number of points per side of the grid
+    pub size: usize,
+    /// The points of the loop in walking order, every point is next to the one before it
+    pub points: Vec<(usize, usize)>,
+}
+
+impl Outline {
+    /// The outline of a random tree of `nodes` cells, grown on a square lattice of `side` cells.
+    ///
+    /// Nodes of the tree are laid out with a gap of one cell between them,
+    /// an edge of the tree fills the gap between its nodes.
+    /// Growing every cell to 2x2 leaves room for points inside the loop.
+    pub fn random(side: usize, nodes: usize, rng: &mut impl Rng) -> Outline {
+        let cells = 2 * side - 1;
+        let mut region = vec![vec![false; cells]; cells];
+        let mut in_tree = vec![vec![false; side]; side];
+
+        // randomized Prim's algorithm
+        let root = (rng.gen_range(0..side), rng.gen_range(0..side));
+        in_tree[root.1][root.0] = true;
+        region[2 * root.1][2 * root.0] = true;
+        let mut count = 1;
+        let mut frontier = neighbours(root, side);
+        while count < nodes && !frontier.is_empty() {
+            let (from, to) = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+            if in_tree[to.1][to.0] {
+                continue;
+            }
+            in_tree[to.1][to.0] = true;
+            region[2 * to.1][2 * to.0] = true;
+            region[from.1 + to.1][from.0 + to.0] = true;
+            count += 1;
+            frontier.extend(neighbours(to, side));
+        }
+
+        let inside = |x: usize, y: usize| {
+            let (x, y) = (x / 2, y / 2);
+            x < cells && y < cells && region[y][x]
+        };
+        // the edge between two points is part of the loop when it separates the inside from the outside,
+        // the cell at (x, y) has the point (x, y) as its top left corner
+        let right = |x: usize, y: usize| inside(x, y) != (y > 0 && inside(x, y - 1));
+        let down = |x: usize, y: usize| inside(x, y) != (x > 0 && inside(x - 1, y));
+
+        // start at the top left corner of the first cell of the tree, and walk right
+        let mut start = (0, 0);
+        'search: for y in 0..2 * cells {
+            for x in 0..2 * cells {
+                if inside(x, y) {
+                    start = (x, y);
+                    break 'search;
+                }
+            }
+        }
+        let mut points = vec![start];
+        let (mut previous, mut current) = (start, (start.0 + 1, start.1));
+        while current != start {
+            points.push(current);
+            let (x, y) = current;
+            let next = [
+                (right(x, y), (x + 1, y)),
+                (x > 0 && right(x - 1, y), (x.wrapping_sub(1), y)),
+                (down(x, y), (x, y + 1)),
+                (y > 0 && down(x, y - 1), (x, y.wrapping_sub(1))),
+            ]
+            .into_iter()
+            .find(|(edge, next)| *edge && *next != previous)
+            .map(|(_edge, next)| next)
+            .unwrap();
+            (previous, current) = (current, next);
+        }
+
+        Outline {
+            size: 2 * cells + 1,
+            points,
+        }
+    }
+}
+
+/// Edges from a node of the lattice to its neighbours
+fn neighbours((x, y): (usize, usize), side: usize) -> Vec<((usize, usize), (usize, usize))> {
+    let mut v = vec![];
+    if x > 0 {
+        v.push(((x, y), (x - 1, y)));
+    }
+    if y > 0 {
+        v.push(((x, y), (x, y - 1)));
+    }
+    if x + 1 < side {
+        v.push(((x, y), (x + 1, y)));
+    }
+    if y + 1 < side {
+        v.push(((x, y), (x, y + 1)));
+    }
+    v
+}
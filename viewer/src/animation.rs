@@ -0,0 +1,62 @@
+/// What a tile shows, every kind is drawn in its own color.
+/// When zoomed out, the tile of the highest kind in a block stands in for the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    /// Nothing of interest
+    Empty,
+    /// Part of the puzzle input the solver never changes, like cube rocks, mirrors or pipes
+    Fixed,
+    /// Something the solver moves around, like rounded rocks
+    Moving,
+    /// Tiles enclosed by what the solver found, like the area inside the loop
+    Inside,
+    /// Tiles the solver has reached: the loop, energized tiles, visited blocks
+    Reached,
+    /// Tiles the solver is about to reach, the frontier of a search
+    Frontier,
+    /// The answer the solver found, like the path with the least heat loss
+    Path,
+    /// Where the solver is right now
+    Current,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 8] = [
+        Kind::Empty,
+        Kind::Fixed,
+        Kind::Moving,
+        Kind::Inside,
+        Kind::Reached,
+        Kind::Frontier,
+        Kind::Path,
+        Kind::Current,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Empty => "empty",
+            Kind::Fixed => "fixed",
+            Kind::Moving => "moving",
+            Kind::Inside => "inside",
+            Kind::Reached => "reached",
+            Kind::Frontier => "frontier",
+            Kind::Path => "path",
+            Kind::Current => "current",
+        }
+    }
+}
+
+/// The states a solver went through, recorded through the hooks of its day
+pub trait Animation {
+    /// Columns and rows of the grid
+    fn size(&self) -> (usize, usize);
+
+    /// Number of states, the first state shows the puzzle input
+    fn len(&self) -> usize;
+
+    /// The tile at (x, y) in the state after `step` steps
+    fn tile(&self, step: usize, x: usize, y: usize) -> (char, Kind);
+
+    /// Describes the state after `step` steps
+    fn status(&self, step: usize) -> String;
+}
@@ -0,0 +1,272 @@
+use std::{
+    io::{self, Stdout},
+    time::Duration,
+};
+
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::animation::{Animation, Kind};
+
+const HELP: &str =
+    "space play/pause  n/p step  f/s faster/slower  +/- zoom  arrows pan  g/G first/last  q quit";
+
+/// Steps through an animation in the terminal
+pub struct App {
+    animation: Box<dyn Animation>,
+    step: usize,
+    playing: bool,
+    /// Steps per frame while playing
+    speed: usize,
+    /// Above zero, every tile is drawn `2 * zoom` characters wide and `zoom` lines high.
+    /// Below zero, every character stands in for a block of `2^-zoom` by `2^-zoom` tiles.
+    zoom: i32,
+    /// The tile in the top left corner of the view
+    offset: (usize, usize),
+}
+
+impl App {
+    pub fn new(animation: Box<dyn Animation>) -> Self {
+        Self {
+            animation,
+            step: 0,
+            playing: false,
+            speed: 1,
+            zoom: 0,
+            offset: (0, 0),
+        }
+    }
+
+    /// Take over the terminal until the user quits
+    pub fn run(mut self) -> Result<()> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen)?;
+        let terminal = Terminal::new(CrosstermBackend::new(io::stdout()));
+
+        // always give the terminal back, even when drawing fails
+        let result = terminal
+            .map_err(Into::into)
+            .and_then(|mut t| self.event_loop(&mut t));
+        disable_raw_mode()?;
+        execute!(io::stdout(), LeaveAlternateScreen)?;
+        result
+    }
+
+    fn event_loop(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if event::poll(Duration::from_millis(30))? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind != KeyEventKind::Press {
+                        continue;
+                    }
+                    if !self.handle_key(key.code, terminal.size()?) {
+                        return Ok(());
+                    }
+                }
+            }
+
+            if self.playing {
+                self.step = (self.step + self.speed).min(self.last_step());
+                self.playing = self.step < self.last_step();
+            }
+        }
+    }
+
+    /// Returns false when the user wants to quit
+    fn handle_key(&mut self, code: KeyCode, area: Rect) -> bool {
+        let (cols, rows) = self.animation.size();
+        // pan by a quarter of the view
+        let (view_cols, view_rows) = self.tiles_in_view(area);
+        let (pan_x, pan_y) = ((view_cols / 4).max(1), (view_rows / 4).max(1));
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char(' ') => {
+                if self.step == self.last_step() {
+                    self.step = 0;
+                }
+                self.playing = !self.playing;
+            }
+            KeyCode::Char('n') => self.step = (self.step + 1).min(self.last_step()),
+            KeyCode::Char('p') => self.step = self.step.saturating_sub(1),
+            KeyCode::Char('g') => self.step = 0,
+            KeyCode::Char('G') => self.step = self.last_step(),
+            KeyCode::Char('f') => self.speed = (self.speed * 2).min(1 << 20),
+            KeyCode::Char('s') => self.speed = (self.speed / 2).max(1),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.zoom = (self.zoom + 1).min(4),
+            KeyCode::Char('-') => self.zoom = (self.zoom - 1).max(-4),
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.offset.0 = self.offset.0.saturating_sub(pan_x)
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.offset.0 = (self.offset.0 + pan_x).min(cols.saturating_sub(1))
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.offset.1 = self.offset.1.saturating_sub(pan_y),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.offset.1 = (self.offset.1 + pan_y).min(rows.saturating_sub(1))
+            }
+            _ => {}
+        }
+        true
+    }
+
+    fn last_step(&self) -> usize {
+        self.animation.len() - 1
+    }
+
+    /// Number of columns and rows of tiles that fit in the grid part of the terminal
+    fn tiles_in_view(&self, area: Rect) -> (usize, usize) {
+        let (width, height) = (area.width as usize, area.height.saturating_sub(3) as usize);
+        if self.zoom > 0 {
+            let zoom = self.zoom as usize;
+            (width / (2 * zoom), height / zoom)
+        } else {
+            let block = 1 << -self.zoom;
+            (width * block, height * block)
+        }
+    }
+
+    pub fn draw(&self, frame: &mut Frame) {
+        let [grid, status] = *Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(frame.size())
+        else {
+            return;
+        };
+
+        frame.render_widget(GridView { app: self }, grid);
+
+        let state = if self.playing { "playing" } else { "paused" };
+        let title = format!(
+            " step {}/{}, {} at {} steps per frame, zoom {} ",
+            self.step,
+            self.last_step(),
+            state,
+            self.speed,
+            self.zoom
+        );
+        let status_line = Paragraph::new(vec![
+            Line::from(self.animation.status(self.step)),
+            Line::styled(HELP, Style::default().fg(Color::DarkGray)),
+        ])
+        .block(Block::default().borders(Borders::TOP).title(title));
+        frame.render_widget(status_line, status);
+    }
+}
+
+/// Draws the tiles of the current step, zoomed in or out
+struct GridView<'a> {
+    app: &'a App,
+}
+
+impl Widget for GridView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let app = self.app;
+        let (cols, rows) = app.animation.size();
+        let (x0, y0) = app.offset;
+
+        for cy in 0..area.height as usize {
+            for cx in 0..area.width as usize {
+                let tile = if app.zoom > 0 {
+                    let zoom = app.zoom as usize;
+                    let (x, y) = (x0 + cx / (2 * zoom), y0 + cy / zoom);
+                    (x < cols && y < rows).then(|| app.animation.tile(app.step, x, y))
+                } else {
+                    // the tile of the highest kind in the block stands in for the block
+                    let block = 1 << -app.zoom;
+                    let (x, y) = (x0 + cx * block, y0 + cy * block);
+                    (y..(y + block).min(rows))
+                        .flat_map(|y| (x..(x + block).min(cols)).map(move |x| (x, y)))
+                        .map(|(x, y)| app.animation.tile(app.step, x, y))
+                        .max_by_key(|(_ch, kind)| *kind)
+                };
+                let Some((ch, kind)) = tile else {
+                    continue;
+                };
+                buf.get_mut(area.x + cx as u16, area.y + cy as u16)
+                    .set_char(ch)
+                    .set_style(style(kind));
+            }
+        }
+    }
+}
+
+fn style(kind: Kind) -> Style {
+    let style = Style::default();
+    match kind {
+        Kind::Empty => style.fg(Color::DarkGray),
+        Kind::Fixed => style.fg(Color::Gray),
+        Kind::Moving => style.fg(Color::Yellow),
+        Kind::Inside => style.fg(Color::Magenta),
+        Kind::Reached => style.fg(Color::LightGreen),
+        Kind::Frontier => style.fg(Color::LightBlue),
+        Kind::Path => style.fg(Color::White).add_modifier(Modifier::BOLD),
+        Kind::Current => style.fg(Color::LightRed).add_modifier(Modifier::BOLD),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+
+    /// A diagonal line that grows by one tile every step
+    struct Diagonal(usize);
+
+    impl Animation for Diagonal {
+        fn size(&self) -> (usize, usize) {
+            (self.0, self.0)
+        }
+
+        fn len(&self) -> usize {
+            self.0 + 1
+        }
+
+        fn tile(&self, step: usize, x: usize, y: usize) -> (char, Kind) {
+            match x == y && x < step {
+                true => ('#', Kind::Reached),
+                false => ('.', Kind::Empty),
+            }
+        }
+
+        fn status(&self, step: usize) -> String {
+            format!("{} tiles", step)
+        }
+    }
+
+    /// Drawing never reaches outside the grid or the terminal, at any zoom level or offset
+    #[test]
+    fn draws_at_every_zoom_level() {
+        let mut terminal = Terminal::new(TestBackend::new(30, 12)).unwrap();
+        let mut app = App::new(Box::new(Diagonal(40)));
+        for zoom in -4..=4 {
+            app.zoom = zoom;
+            for offset in [(0, 0), (39, 39)] {
+                app.offset = offset;
+                app.step = app.last_step();
+                terminal.draw(|frame| app.draw(frame)).unwrap();
+            }
+        }
+
+        // zoomed out, the line stands out from the empty tiles around it
+        app.zoom = -2;
+        app.offset = (0, 0);
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer.get(0, 0).symbol(), "#");
+        assert_eq!(buffer.get(1, 1).symbol(), "#");
+        assert_eq!(buffer.get(1, 0).symbol(), ".");
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use day_10::Grid;
+
+use crate::animation::{Animation, Kind};
+
+/// The walk along the loop of pipes, one pipe at a time, ending with the tiles enclosed by the loop
+pub struct LoopWalk {
+    grid: Grid,
+    /// For every tile, the step at which the walk reached it
+    reached: Vec<Vec<Option<usize>>>,
+    enclosed: HashSet<(i32, i32)>,
+    /// Length of the loop
+    pipes: usize,
+}
+
+impl LoopWalk {
+    pub fn record(input: &str) -> Result<Self> {
+        let grid = Grid::parse_input(input)?;
+        let start = grid
+            .find_char('S')
+            .context("Expected a starting position")?;
+
+        let mut reached = vec![vec![None; grid.cols()]; grid.rows()];
+        let mut pipes = 0;
+        let positions = grid.walk_loop(start, |(x, y)| {
+            pipes += 1;
+            reached[y as usize][x as usize] = Some(pipes);
+        });
+        let enclosed = grid.enclosed(&positions);
+        Ok(Self {
+            grid,
+            reached,
+            enclosed,
+            pipes,
+        })
+    }
+}
+
+impl Animation for LoopWalk {
+    fn size(&self) -> (usize, usize) {
+        (self.grid.cols(), self.grid.rows())
+    }
+
+    fn len(&self) -> usize {
+        self.pipes + 1
+    }
+
+    fn tile(&self, step: usize, x: usize, y: usize) -> (char, Kind) {
+        let ch = match self.grid.data[y][x] {
+            '|' => '│',
+            '-' => '─',
+            'L' => '└',
+            'J' => '┘',
+            '7' => '┐',
+            'F' => '┌',
+            ch => ch,
+        };
+        let kind = match self.reached[y][x] {
+            Some(reached) if reached == step => Kind::Current,
+            Some(reached) if reached < step => Kind::Reached,
+            // once the loop is closed, show what it encloses
+            None if step == self.pipes && self.enclosed.contains(&(x as i32, y as i32)) => {
+                Kind::Inside
+            }
+            _ if ch == '.' => Kind::Empty,
+            _ => Kind::Fixed,
+        };
+        (ch, kind)
+    }
+
+    fn status(&self, step: usize) -> String {
+        let status = format!(
+            "walked {} of {} pipes, the farthest pipe is {} steps from S",
+            step,
+            self.pipes,
+            self.pipes / 2
+        );
+        match step == self.pipes {
+            true => format!("{}, enclosing {} tiles", status, self.enclosed.len()),
+            false => status,
+        }
+    }
+}
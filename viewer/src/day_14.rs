@@ -0,0 +1,62 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use day_14::{Platform, Record};
+
+use crate::animation::{Animation, Kind};
+
+const TILTS: [&str; 4] = ["north", "west", "south", "east"];
+
+/// The platform after every tilt of the spin cycles, until the platform repeats itself
+pub struct SpinCycles {
+    platforms: Vec<Platform>,
+}
+
+impl SpinCycles {
+    /// Spin for atmost `max_cycles` cycles
+    pub fn record(input: &str, max_cycles: usize) -> Result<Self> {
+        let mut platform = Platform::parse_platform(input)?;
+        let mut platforms = vec![platform.clone()];
+        let mut seen = HashSet::new();
+
+        for _ in 0..max_cycles {
+            platform.spin_cycle(|p| platforms.push(p.clone()));
+            if !seen.insert(platform.grid.clone()) {
+                break;
+            }
+        }
+        Ok(Self { platforms })
+    }
+}
+
+impl Animation for SpinCycles {
+    fn size(&self) -> (usize, usize) {
+        let grid = &self.platforms[0].grid;
+        (grid[0].len(), grid.len())
+    }
+
+    fn len(&self) -> usize {
+        self.platforms.len()
+    }
+
+    fn tile(&self, step: usize, x: usize, y: usize) -> (char, Kind) {
+        match self.platforms[step].grid[y][x] {
+            Record::Round => ('O', Kind::Moving),
+            Record::Cubic => ('#', Kind::Fixed),
+            Record::Empty => ('.', Kind::Empty),
+        }
+    }
+
+    fn status(&self, step: usize) -> String {
+        let load = self.platforms[step].compute_total_load();
+        if step == 0 {
+            return format!("before spinning, load {}", load);
+        }
+        format!(
+            "cycle {}, tilted {}, load {}",
+            step.div_ceil(4),
+            TILTS[(step - 1) % 4],
+            load
+        )
+    }
+}
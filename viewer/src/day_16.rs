@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use day_16::{mark_tiles, BeamDirection, Grid, Tile};
+
+use crate::animation::{Animation, Kind};
+
+/// The beam entering the top left tile heading east, one tile at a time
+pub struct Beams {
+    grid: Grid,
+    /// Every tile a beam entered, in order
+    steps: Vec<(usize, usize)>,
+    /// For every tile, the step at which it was energized
+    energized: Vec<Vec<Option<usize>>>,
+    /// The number of energized tiles after every step
+    counts: Vec<usize>,
+}
+
+impl Beams {
+    pub fn record(input: &str) -> Result<Self> {
+        let grid = Grid::parse_grid(input)?;
+        let mut steps = vec![];
+        let mut energized = vec![vec![None; grid.cols]; grid.rows];
+        let mut counts = vec![0];
+
+        mark_tiles(
+            &grid,
+            Some((0, 0)),
+            BeamDirection::East,
+            &mut HashMap::new(),
+            &mut |(x, y), _beam_dir| {
+                steps.push((x, y));
+                let mut count = *counts.last().unwrap();
+                if energized[y][x].is_none() {
+                    energized[y][x] = Some(steps.len());
+                    count += 1;
+                }
+                counts.push(count);
+            },
+        );
+        Ok(Self {
+            grid,
+            steps,
+            energized,
+            counts,
+        })
+    }
+}
+
+impl Animation for Beams {
+    fn size(&self) -> (usize, usize) {
+        (self.grid.cols, self.grid.rows)
+    }
+
+    fn len(&self) -> usize {
+        self.steps.len() + 1
+    }
+
+    fn tile(&self, step: usize, x: usize, y: usize) -> (char, Kind) {
+        let tile = self.grid.tiles[y][x];
+        let energized = self.energized[y][x].is_some_and(|energized| energized <= step);
+        let ch = if tile == Tile::Empty && energized {
+            '#'
+        } else {
+            tile.to_string().chars().next().unwrap()
+        };
+        let kind = if step > 0 && self.steps[step - 1] == (x, y) {
+            Kind::Current
+        } else if energized {
+            Kind::Reached
+        } else if tile == Tile::Empty {
+            Kind::Empty
+        } else {
+            Kind::Fixed
+        };
+        (ch, kind)
+    }
+
+    fn status(&self, step: usize) -> String {
+        format!(
+            "step {} of {}, {} tiles energized",
+            step,
+            self.steps.len(),
+            self.counts[step]
+        )
+    }
+}
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use day_17::{dijkstra, least_heat_path, Event, Grid};
+
+use crate::animation::{Animation, Kind};
+
+/// The search for the path with the least heat loss, one queued or visited path at a time,
+/// ending with the path it found
+pub struct Search {
+    grid: Grid,
+    events: Vec<Event>,
+    /// For every block, whether the path with the least heat loss passes it
+    path: Vec<Vec<bool>>,
+    /// For every block, the step at which a path to it was first queued
+    queued: Vec<Vec<Option<usize>>>,
+    /// For every block, the step at which a path to it was first visited
+    visited: Vec<Vec<Option<usize>>>,
+    heat_loss: u32,
+}
+
+impl Search {
+    /// Search for the path of a crucible, or an ultra crucible for part two
+    pub fn record(input: &str, part_two: bool) -> Result<Self> {
+        let grid = Grid::parse_grid(input)?;
+        let (min_steps, max_steps) = if part_two { (4, 10) } else { (1, 3) };
+
+        let mut events = vec![];
+        // the grid names the number of lines its columns, so size by its data
+        let (cols, rows) = (grid.data[0].len(), grid.data.len());
+        let mut queued = vec![vec![None; cols]; rows];
+        let mut visited = vec![vec![None; cols]; rows];
+        let heat_loss = dijkstra(&grid, min_steps, max_steps, |event| {
+            events.push(event);
+            let (position, first) = match event {
+                Event::Queued(position, _, _) => (position, &mut queued),
+                Event::Visited(position, _, _) => (position, &mut visited),
+            };
+            first[position.y][position.x].get_or_insert(events.len());
+        });
+
+        let mut path = vec![vec![false; cols]; rows];
+        for position in least_heat_path(&grid, min_steps, max_steps).1 {
+            path[position.y][position.x] = true;
+        }
+        Ok(Self {
+            grid,
+            events,
+            path,
+            queued,
+            visited,
+            heat_loss,
+        })
+    }
+}
+
+impl Animation for Search {
+    fn size(&self) -> (usize, usize) {
+        (self.grid.data[0].len(), self.grid.data.len())
+    }
+
+    fn len(&self) -> usize {
+        self.events.len() + 2
+    }
+
+    fn tile(&self, step: usize, x: usize, y: usize) -> (char, Kind) {
+        let ch = char::from_digit(self.grid.data[y][x], 10).unwrap_or('?');
+        let before =
+            |first: &Vec<Vec<Option<usize>>>| first[y][x].is_some_and(|first| first <= step);
+        let current = step > 0
+            && step <= self.events.len()
+            && match self.events[step - 1] {
+                Event::Queued(position, _, _) | Event::Visited(position, _, _) => {
+                    position.loc() == (x, y)
+                }
+            };
+        let kind = if step > self.events.len() && self.path[y][x] {
+            Kind::Path
+        } else if current {
+            Kind::Current
+        } else if before(&self.visited) {
+            Kind::Reached
+        } else if before(&self.queued) {
+            Kind::Frontier
+        } else {
+            Kind::Empty
+        };
+        (ch, kind)
+    }
+
+    fn status(&self, step: usize) -> String {
+        let event = match step.checked_sub(1).map(|i| self.events.get(i)) {
+            Some(Some(Event::Queued(position, _, cost))) => {
+                format!(
+                    "queued ({}, {}) with heat loss {}",
+                    position.x, position.y, cost
+                )
+            }
+            Some(Some(Event::Visited(position, _, cost))) => {
+                format!(
+                    "visited ({}, {}) with heat loss {}",
+                    position.x, position.y, cost
+                )
+            }
+            Some(None) => "the path with the least heat loss".to_string(),
+            None => "before searching".to_string(),
+        };
+        format!(
+            "step {} of {}, {}, least heat loss {}",
+            step,
+            self.len() - 1,
+            event,
+            self.heat_loss
+        )
+    }
+}
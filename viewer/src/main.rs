@@ -0,0 +1,179 @@
+//! Step through the grids of days 10, 14, 16 and 17 in the terminal,
+//! as recorded through the hooks of their solvers.
+//!
+//! Instead of the terminal, a state can be written as a PNG, or all states as an animated GIF:
+//!
+//! ```sh
+//! cargo run --release -p viewer -- 17 --gif search.gif --frames 200 --palette path=ff0000
+//! ```
+mod animation;
+mod app;
+mod day_10;
+mod day_14;
+mod day_16;
+mod day_17;
+mod render;
+
+use std::{env, io, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use aoc_input_lib::get_puzzle_input;
+use clap::Parser;
+
+use animation::Animation;
+use app::App;
+use render::{Palette, PaletteEntry};
+
+/// Terminal viewer for the grid puzzles
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    /// The day to view: 10, 14, 16 or 17
+    day: u32,
+
+    /// The puzzle input, or - to read it from stdin
+    #[arg()]
+    input: Option<String>,
+
+    /// Advent of code session token
+    #[arg(short, long)]
+    session: Option<String>,
+
+    /// View the search of part two, for day 17
+    #[arg(long)]
+    part_two: bool,
+
+    /// Stop spinning the platform of day 14 after this many cycles
+    #[arg(long, default_value_t = 200)]
+    max_cycles: usize,
+
+    /// Write the state after --step steps as a PNG, instead of viewing it in the terminal
+    #[arg(long)]
+    png: Option<PathBuf>,
+
+    /// Write the states as an animated GIF, instead of viewing them in the terminal
+    #[arg(long)]
+    gif: Option<PathBuf>,
+
+    /// The step to write as a PNG, defaults to the last step
+    #[arg(long)]
+    step: Option<usize>,
+
+    /// The number of states to write as a GIF, evenly spread from the first to the last state
+    #[arg(long, default_value_t = 100)]
+    frames: usize,
+
+    /// Milliseconds to show every frame of the GIF
+    #[arg(long, default_value_t = 50)]
+    delay: u16,
+
+    /// Pixels per side of a tile in the images
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..))]
+    scale: u16,
+
+    /// Colors of the images, as kind=rrggbb separated by commas.
+    /// The kinds are empty, fixed, moving, inside, reached, frontier, path and current
+    #[arg(long, value_delimiter = ',')]
+    palette: Vec<PaletteEntry>,
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let session = args.session.or_else(|| env::var("SESSION").ok());
+
+    let input = match args.input {
+        Some(s) if s == "-" => io::read_to_string(io::stdin())?,
+        Some(s) => s,
+        None => {
+            get_puzzle_input(2023, args.day, session).context("Could not retrieve puzzle input")?
+        }
+    };
+
+    let animation = record(args.day, &input, args.part_two, args.max_cycles)?;
+    if args.png.is_none() && args.gif.is_none() {
+        return App::new(animation).run();
+    }
+
+    let mut palette = Palette::default();
+    for PaletteEntry(kind, color) in args.palette {
+        palette.set(kind, color);
+    }
+    let scale = args.scale as usize;
+    if let Some(path) = args.png {
+        let step = args.step.unwrap_or(usize::MAX).min(animation.len() - 1);
+        render::write_png(&path, animation.as_ref(), step, &palette, scale)?;
+    }
+    if let Some(path) = args.gif {
+        render::write_gif(
+            &path,
+            animation.as_ref(),
+            args.frames,
+            args.delay,
+            &palette,
+            scale,
+        )?;
+    }
+    Ok(())
+}
+
+fn record(day: u32, input: &str, part_two: bool, max_cycles: usize) -> Result<Box<dyn Animation>> {
+    Ok(match day {
+        10 => Box::new(day_10::LoopWalk::record(input)?),
+        14 => Box::new(day_14::SpinCycles::record(input, max_cycles)?),
+        16 => Box::new(day_16::Beams::record(input)?),
+        17 => Box::new(day_17::Search::record(input, part_two)?),
+        _ => bail!("Expected day 10, 14, 16 or 17, got {}", day),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::Kind;
+
+    const LOOP: &str = ".....\n.S-7.\n.|.|.\n.L-J.\n.....\n";
+    const PLATFORM: &str = "O....#....\nO.OO#....#\n.....##...\nOO.#O....O\n.O.....O#.\nO.#..O.#.#\n..O..#O..O\n.......O..\n#....###..\n#OO..#....\n";
+    const CONTRAPTION: &str = ".|...\\....\n|.-.\\.....\n.....|-...\n........|.\n..........\n.........\\\n..../.\\\\..\n.-.-/..|..\n.|....-|.\\\n..//.|....\n";
+    const CITY: &str = "2413432311323\n3215453535623\n3255245654254\n3446585845452\n4546657867536\n1438598798454\n4457876987766\n3637877979653\n4654967986887\n4564679986453\n1224686865563\n2546548887735\n4322674655533\n";
+
+    /// Count the tiles of the given kinds after the last step
+    fn count_last(animation: &dyn Animation, kinds: &[Kind]) -> usize {
+        let (cols, rows) = animation.size();
+        (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| (x, y)))
+            .filter(|(x, y)| kinds.contains(&animation.tile(animation.len() - 1, *x, *y).1))
+            .count()
+    }
+
+    /// The recorded animations end where the solvers do
+    #[test]
+    fn example_animations() {
+        let reached = [Kind::Reached, Kind::Current];
+        let walk = record(10, LOOP, false, 0).unwrap();
+        assert_eq!(walk.len(), 9);
+        assert_eq!(count_last(walk.as_ref(), &reached), 8);
+        assert_eq!(count_last(walk.as_ref(), &[Kind::Inside]), 1);
+        assert!(walk.status(8).ends_with("enclosing 1 tiles"));
+
+        let spins = record(14, PLATFORM, false, 1).unwrap();
+        assert_eq!(spins.len(), 5);
+        assert!(spins.status(4).ends_with("load 87"));
+
+        let beams = record(16, CONTRAPTION, false, 0).unwrap();
+        assert_eq!(count_last(beams.as_ref(), &reached), 46);
+
+        for part_two in [false, true] {
+            let search = record(17, CITY, part_two, 0).unwrap();
+            let least = if part_two { "94" } else { "102" };
+            assert!(search.status(0).ends_with(least));
+
+            // the heat loss along the path adds up to the least heat loss, the start is free
+            let path = (0..13)
+                .flat_map(|y| (0..13).map(move |x| (x, y)))
+                .filter(|&(x, y)| search.tile(search.len() - 1, x, y).1 == Kind::Path)
+                .map(|(x, y)| search.tile(0, x, y).0.to_digit(10).unwrap())
+                .sum::<u32>();
+            assert_eq!(path - 2, least.parse::<u32>().unwrap());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::{fs::File, io::BufWriter, iter, path::Path, str::FromStr};
+
+use anyhow::{bail, Context, Error, Result};
+
+use crate::animation::{Animation, Kind};
+
+/// The color of every kind of tile, as red, green and blue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: [[u8; 3]; Kind::ALL.len()],
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            colors: [
+                [0x1e, 0x1e, 0x2e], // empty
+                [0x6c, 0x70, 0x86], // fixed
+                [0xf9, 0xe2, 0xaf], // moving
+                [0x58, 0x3d, 0x7a], // inside
+                [0xa6, 0xe3, 0xa1], // reached
+                [0x89, 0xb4, 0xfa], // frontier
+                [0xff, 0xff, 0xff], // path
+                [0xf3, 0x8b, 0xa8], // current
+            ],
+        }
+    }
+}
+
+impl Palette {
+    /// Draw `kind` in `color` from now on
+    pub fn set(&mut self, kind: Kind, color: [u8; 3]) {
+        self.colors[kind as usize] = color;
+    }
+
+    /// The palette as consecutive red, green and blue bytes, indexed by kind
+    fn rgb(&self) -> Vec<u8> {
+        self.colors.concat()
+    }
+}
+
+/// Overrides the color of one kind of tile, written as `kind=rrggbb`, like `path=ff0000`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaletteEntry(pub Kind, pub [u8; 3]);
+
+impl FromStr for PaletteEntry {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, color) = s
+            .split_once('=')
+            .with_context(|| format!("Expected kind=rrggbb, got: {}", s))?;
+        let kind = Kind::ALL
+            .into_iter()
+            .find(|kind| kind.name() == name)
+            .with_context(|| {
+                let names = Kind::ALL.map(|kind| kind.name()).join(", ");
+                format!("Expected one of {}, got: {}", names, name)
+            })?;
+
+        let color = color.trim_start_matches('#');
+        if color.len() != 6 || !color.is_ascii() {
+            bail!("Expected a color of 6 hexadecimal digits, got: {}", color);
+        }
+        let mut rgb = [0; 3];
+        for (i, channel) in rgb.iter_mut().enumerate() {
+            *channel = u8::from_str_radix(&color[2 * i..2 * i + 2], 16)
+                .with_context(|| format!("Expected hexadecimal digits, got: {}", color))?;
+        }
+        Ok(PaletteEntry(kind, rgb))
+    }
+}
+
+/// An image of the state after `step` steps, every tile `scale` by `scale` pixels.
+/// Returns the width, the height and the kind of every pixel, row by row.
+fn pixels(animation: &dyn Animation, step: usize, scale: usize) -> (usize, usize, Vec<u8>) {
+    let (cols, rows) = animation.size();
+    let (width, height) = (cols * scale, rows * scale);
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..rows {
+        let row = (0..cols)
+            .flat_map(|x| {
+                let (_ch, kind) = animation.tile(step, x, y);
+                iter::repeat_n(kind as u8, scale)
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..scale {
+            pixels.extend_from_slice(&row);
+        }
+    }
+    (width, height, pixels)
+}
+
+/// Write the state after `step` steps as a PNG
+pub fn write_png(
+    path: &Path,
+    animation: &dyn Animation,
+    step: usize,
+    palette: &Palette,
+    scale: usize,
+) -> Result<()> {
+    let (width, height, pixels) = pixels(animation, step, scale);
+    let file =
+        File::create(path).with_context(|| format!("Could not create {}", path.display()))?;
+
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(file),
+        width
+            .try_into()
+            .context("The image is too wide for a PNG")?,
+        height
+            .try_into()
+            .context("The image is too high for a PNG")?,
+    );
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(palette.rgb());
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Write atmost `frames` states, evenly spread from the first to the last state, as an animated GIF.
+/// Every frame is shown for `delay` milliseconds, the last one for a second longer.
+pub fn write_gif(
+    path: &Path,
+    animation: &dyn Animation,
+    frames: usize,
+    delay: u16,
+    palette: &Palette,
+    scale: usize,
+) -> Result<()> {
+    let (cols, rows) = animation.size();
+    let too_large = || format!("A GIF is atmost {} pixels wide and high", u16::MAX);
+    let width = u16::try_from(cols * scale).with_context(too_large)?;
+    let height = u16::try_from(rows * scale).with_context(too_large)?;
+    let file =
+        File::create(path).with_context(|| format!("Could not create {}", path.display()))?;
+
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &palette.rgb())?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    let steps = spread(animation.len() - 1, frames);
+    for (i, &step) in steps.iter().enumerate() {
+        let (_width, _height, pixels) = pixels(animation, step, scale);
+        let mut frame = gif::Frame::from_indexed_pixels(width, height, &pixels, None);
+        // the delay of a frame is in hundredths of a second
+        frame.delay = delay / 10 + if i + 1 == steps.len() { 100 } else { 0 };
+        encoder.write_frame(&frame)?;
+    }
+    Ok(())
+}
+
+/// Atmost `frames` steps from 0 to `last`, evenly spread and always including the last step
+fn spread(last: usize, frames: usize) -> Vec<usize> {
+    if frames <= 1 || last == 0 {
+        return vec![last];
+    }
+    let mut steps = (0..frames)
+        .map(|i| (i as u128 * last as u128 / (frames - 1) as u128) as usize)
+        .collect::<Vec<_>>();
+    steps.dedup();
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A board of 3 by 2 tiles that fills up one tile every step
+    struct Board;
+
+    impl Animation for Board {
+        fn size(&self) -> (usize, usize) {
+            (3, 2)
+        }
+
+        fn len(&self) -> usize {
+            7
+        }
+
+        fn tile(&self, step: usize, x: usize, y: usize) -> (char, Kind) {
+            match y * 3 + x < step {
+                true => ('#', Kind::Reached),
+                false => ('.', Kind::Empty),
+            }
+        }
+
+        fn status(&self, step: usize) -> String {
+            format!("{} tiles", step)
+        }
+    }
+
+    #[test]
+    fn parses_palette_entries() {
+        let entry = "path=#ff8000".parse::<PaletteEntry>().unwrap();
+        assert_eq!(entry, PaletteEntry(Kind::Path, [0xff, 0x80, 0x00]));
+        assert!("path=ff80".parse::<PaletteEntry>().is_err());
+        assert!("path=gg8000".parse::<PaletteEntry>().is_err());
+        assert!("rocks=ff8000".parse::<PaletteEntry>().is_err());
+    }
+
+    #[test]
+    fn spreads_frames_evenly() {
+        assert_eq!(spread(10, 3), [0, 5, 10]);
+        assert_eq!(spread(2, 10), [0, 1, 2]);
+        assert_eq!(spread(10, 1), [10]);
+        assert_eq!(spread(0, 5), [0]);
+    }
+
+    /// The written images decode to the colors of the palette, tile by tile
+    #[test]
+    fn writes_png_and_gif() {
+        let dir = std::env::temp_dir();
+        let mut palette = Palette::default();
+        palette.set(Kind::Reached, [0xff, 0, 0]);
+
+        let path = dir.join(format!("viewer-{}.png", std::process::id()));
+        write_png(&path, &Board, 4, &palette, 2).unwrap();
+        let decoder = png::Decoder::new(File::open(&path).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        // the first row of the second row of tiles: one reached tile, two empty ones
+        assert_eq!(&buf[12..18], [4, 4, 0, 0, 0, 0]);
+        assert_eq!(reader.info().palette.as_deref(), Some(&palette.rgb()[..]));
+        std::fs::remove_file(&path).unwrap();
+
+        let path = dir.join(format!("viewer-{}.gif", std::process::id()));
+        write_gif(&path, &Board, 4, 50, &palette, 1).unwrap();
+        let mut decoder = gif::DecodeOptions::new();
+        decoder.set_color_output(gif::ColorOutput::Indexed);
+        let mut decoder = decoder.read_info(File::open(&path).unwrap()).unwrap();
+        let mut frames = vec![];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            frames.push((frame.delay, frame.buffer.to_vec()));
+        }
+        assert_eq!(frames.len(), 4);
+        assert_eq!(frames[0], (5, vec![0; 6]));
+        assert_eq!(frames[3], (105, vec![4; 6]));
+        std::fs::remove_file(&path).unwrap();
+    }
+}